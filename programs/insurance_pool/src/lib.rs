@@ -16,6 +16,7 @@ pub mod insurance_pool {
         pool.rejected_claims = 0;
         pool.total_paid_out = 0;
         pool.coverage_limit = coverage_limit;
+        pool.pending_authority = None;
         pool.bump = ctx.bumps.pool;
         
         msg!("Insurance pool initialized with coverage limit: {}", coverage_limit);
@@ -163,6 +164,67 @@ pub mod insurance_pool {
         msg!("Coverage limit updated to: {}", new_limit);
         Ok(())
     }
+
+    /// Propose a new pool authority (authority only)
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.pending_authority = Some(new_authority);
+        
+        msg!("Authority transfer proposed: {} -> {}", pool.authority, new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.authority = ctx.accounts.new_authority.key();
+        pool.pending_authority = None;
+        
+        msg!("Authority transferred to: {}", pool.authority);
+        Ok(())
+    }
+
+    /// Grow a pool created by an earlier deployment to the current layout (authority only)
+    /// Appended fields start zeroed, i.e. no pending authority.
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        let pool = ctx.accounts.pool.to_account_info();
+        {
+            let data = pool.try_borrow_data()?;
+            require!(
+                data.starts_with(&<InsurancePool as anchor_lang::Discriminator>::DISCRIMINATOR),
+                anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+            );
+            require!(
+                data.get(8..40) == Some(ctx.accounts.authority.key().as_ref()),
+                ErrorCode::Unauthorized
+            );
+        }
+        
+        let old_len = pool.data_len();
+        let new_len = 8 + InsurancePool::INIT_SPACE;
+        require!(old_len < new_len, ErrorCode::AlreadyMigrated);
+        
+        // Only the extra rent is paid in, so pool deposits are left untouched
+        let rent = Rent::get()?;
+        let top_up = rent.minimum_balance(new_len).saturating_sub(rent.minimum_balance(old_len));
+        if top_up > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: pool.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        // anchor-lang 0.30 predates AccountInfo::resize (and trust_token's grow_account)
+        pool.realloc(new_len, true)?;
+        
+        msg!("Insurance pool migrated: {} -> {} bytes", old_len, new_len);
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -293,6 +355,49 @@ pub struct UpdateCoverageLimit<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_pool"],
+        bump = pool.bump,
+        constraint = authority.key() == pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub pool: Account<'info, InsurancePool>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_pool"],
+        bump = pool.bump,
+        constraint = pool.pending_authority == Some(new_authority.key()) @ ErrorCode::NotPendingAuthority
+    )]
+    pub pool: Account<'info, InsurancePool>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// CHECK: Cannot be deserialized before migration; discriminator and authority are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"insurance_pool"],
+        bump,
+        owner = crate::ID
+    )]
+    pub pool: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct InsurancePool {
@@ -304,6 +409,7 @@ pub struct InsurancePool {
     pub total_paid_out: u64,
     pub coverage_limit: u64,
     pub bump: u8,
+    pub pending_authority: Option<Pubkey>,
 }
 
 #[account]
@@ -364,4 +470,8 @@ pub enum ErrorCode {
     InvalidClaimant,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
 }
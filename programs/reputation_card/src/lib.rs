@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use trust_token::{grow_account, Identity, IdentityLink, TrustToken, VerificationLevel};
 
 // Program ID from devnet deployment
 declare_id!("FmZBiFUHHtGQioU11V9asYiJGP5wDdrLqoikzAyEyRtc");
@@ -21,11 +22,301 @@ pub mod reputation_card {
         program_state.authority = ctx.accounts.authority.key();
         program_state.total_cards_issued = 0;
         program_state.total_cards_revoked = 0;
+        program_state.pending_authority = None;
+        program_state.require_verified_recipient = false;
+        program_state.card_cooldown = ProgramState::DEFAULT_CARD_COOLDOWN;
+        program_state.daily_card_cap = ProgramState::DEFAULT_DAILY_CARD_CAP;
+        program_state.score_half_life = ProgramState::DEFAULT_SCORE_HALF_LIFE;
+        program_state.marketplace_authority = None;
+        program_state.dispute_window = ProgramState::DEFAULT_DISPUTE_WINDOW;
+        program_state.dispute_moderator = None;
         
         msg!("ReputationCard program initialized by authority: {}", program_state.authority);
         Ok(())
     }
 
+    /// Propose a new program authority
+    /// First step of a two-step handover; the proposed key must call `accept_authority`.
+    /// Proposing again replaces any pending proposal.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the proposal
+    /// * `new_authority` - The key that will become the authority once it accepts
+    /// 
+    /// # Security
+    /// - Only the current program authority can propose a successor
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        // Security check: Only authority can propose a successor
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            ReputationCardError::UnauthorizedConfig
+        );
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.pending_authority = Some(new_authority);
+
+        msg!("Authority transfer proposed: {} → {}", program_state.authority, new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer
+    /// Second step of the handover; the signer becomes the program authority.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for acceptance
+    /// 
+    /// # Security
+    /// - Only the pending authority can accept
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let program_state = &mut ctx.accounts.program_state;
+
+        // Security check: Only the proposed key can accept
+        require!(
+            program_state.pending_authority == Some(ctx.accounts.new_authority.key()),
+            ReputationCardError::NotPendingAuthority
+        );
+
+        let old_authority = program_state.authority;
+        program_state.authority = ctx.accounts.new_authority.key();
+        program_state.pending_authority = None;
+
+        msg!("Authority transferred: {} → {}", old_authority, program_state.authority);
+        Ok(())
+    }
+
+    /// Migrate the program state account to the current layout
+    /// A `ProgramState` created by an earlier deployment is shorter than `ProgramState::LEN`
+    /// and no longer deserializes. This grows it in place and gives the appended fields
+    /// the same defaults as `initialize`.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the program state and the paying authority
+    /// 
+    /// # Security
+    /// - Only the authority recorded in the old account can migrate it
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        let program_state_info = ctx.accounts.program_state.to_account_info();
+        {
+            let data = program_state_info.try_borrow_data()?;
+            require!(
+                data.starts_with(ProgramState::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            // Security check: Only authority can migrate (the first field in every layout)
+            require!(
+                data.get(8..40) == Some(ctx.accounts.authority.key().as_ref()),
+                ReputationCardError::UnauthorizedConfig
+            );
+        }
+
+        let old_len = program_state_info.data_len();
+        require!(old_len < ProgramState::LEN, ReputationCardError::AlreadyMigrated);
+        grow_account(
+            &program_state_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ProgramState::LEN,
+        )?;
+
+        let mut data = program_state_info.try_borrow_mut_data()?;
+        let mut program_state = ProgramState::try_deserialize(&mut &data[..])?;
+        program_state.card_cooldown = ProgramState::DEFAULT_CARD_COOLDOWN;
+        program_state.daily_card_cap = ProgramState::DEFAULT_DAILY_CARD_CAP;
+        program_state.score_half_life = ProgramState::DEFAULT_SCORE_HALF_LIFE;
+        program_state.dispute_window = ProgramState::DEFAULT_DISPUTE_WINDOW;
+        program_state.try_serialize(&mut &mut data[..])?;
+
+        msg!("Program state migrated: {} → {} bytes", old_len, ProgramState::LEN);
+        Ok(())
+    }
+
+    /// Migrate a reputation card to the current layout
+    /// Grows a card created by an earlier deployment to `ReputationCard::LEN` (the appended
+    /// fields start empty: no order reference, no dispute history, never amended) and counts
+    /// it in the recipient's profile, which did not exist when the card was issued.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the card, the recipient's profile and the payer
+    /// 
+    /// # Security
+    /// - Permissionless: the payer only covers rent, every stored value comes from the old account
+    /// - Each card is migrated, and so counted, at most once
+    pub fn migrate_card(ctx: Context<MigrateCard>) -> Result<()> {
+        let card_info = ctx.accounts.reputation_card.to_account_info();
+        {
+            let data = card_info.try_borrow_data()?;
+            require!(
+                data.starts_with(ReputationCard::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            // The profile is seeded by `recipient`, so it must be the recipient stored in the card
+            require!(
+                data.get(40..72) == Some(ctx.accounts.recipient.key().as_ref()),
+                ReputationCardError::InvalidRecipient
+            );
+        }
+
+        require!(
+            card_info.data_len() < ReputationCard::LEN,
+            ReputationCardError::AlreadyMigrated
+        );
+        grow_account(
+            &card_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ReputationCard::LEN,
+        )?;
+        let card = ReputationCard::try_deserialize(&mut &card_info.try_borrow_data()?[..])?;
+
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.recipient = card.recipient;
+        profile.add_card(&card)?;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("Reputation card #{} migrated", card.card_number);
+        Ok(())
+    }
+
+    /// Set whether card recipients must also hold a verified TrustToken
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `require_verified_recipient` - Whether `create_card` checks the recipient's TrustToken
+    /// 
+    /// # Security
+    /// - Only the program authority can change this setting
+    pub fn set_require_verified_recipient(
+        ctx: Context<SetRequireVerifiedRecipient>,
+        require_verified_recipient: bool,
+    ) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            ReputationCardError::UnauthorizedConfig
+        );
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.require_verified_recipient = require_verified_recipient;
+
+        msg!("Require verified recipient set to {}", require_verified_recipient);
+        Ok(())
+    }
+
+    /// Set the marketplace key that signs purchase receipts
+    /// Cards created with an `order_ref` must be co-signed by this key.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `marketplace_authority` - The receipt signer, or `None` to stop accepting order references
+    /// 
+    /// # Security
+    /// - Only the program authority can change this setting
+    pub fn set_marketplace_authority(
+        ctx: Context<SetMarketplaceAuthority>,
+        marketplace_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            ReputationCardError::UnauthorizedConfig
+        );
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.marketplace_authority = marketplace_authority;
+
+        msg!("Marketplace authority set to {:?}", marketplace_authority);
+        Ok(())
+    }
+
+    /// Configure dispute handling
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `dispute_window` - Seconds a dispute stays open before the card reverts to Active
+    /// * `dispute_moderator` - Key that can resolve disputes besides the authority
+    /// 
+    /// # Security
+    /// - Only the program authority can change this setting
+    pub fn set_dispute_config(
+        ctx: Context<SetDisputeConfig>,
+        dispute_window: i64,
+        dispute_moderator: Option<Pubkey>,
+    ) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            ReputationCardError::UnauthorizedConfig
+        );
+        require!(dispute_window > 0, ReputationCardError::InvalidDisputeWindow);
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.dispute_window = dispute_window;
+        program_state.dispute_moderator = dispute_moderator;
+
+        msg!(
+            "Dispute config set: window {} seconds, moderator {:?}",
+            dispute_window,
+            dispute_moderator
+        );
+        Ok(())
+    }
+
+    /// Set the anti-spam limits on card issuance
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `card_cooldown` - Seconds before an issuer can give the same recipient another card of the same type
+    /// * `daily_card_cap` - Maximum cards one issuer can create per UTC day (0 = unlimited)
+    /// 
+    /// # Security
+    /// - Only the program authority can change the limits
+    pub fn set_issuance_limits(
+        ctx: Context<SetIssuanceLimits>,
+        card_cooldown: i64,
+        daily_card_cap: u32,
+    ) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            ReputationCardError::UnauthorizedConfig
+        );
+        require!(card_cooldown >= 0, ReputationCardError::InvalidCooldown);
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.card_cooldown = card_cooldown;
+        program_state.daily_card_cap = daily_card_cap;
+
+        msg!(
+            "Issuance limits set: cooldown {} seconds, {} cards per day",
+            card_cooldown,
+            daily_card_cap
+        );
+        Ok(())
+    }
+
+    /// Set the half-life of weighted reputation scores
+    /// A card's weight halves every `score_half_life` seconds after it was issued.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the program state
+    /// * `score_half_life` - Half-life in seconds (0 disables decay)
+    /// 
+    /// # Security
+    /// - Only the program authority can change configuration
+    pub fn set_score_half_life(ctx: Context<SetScoreHalfLife>, score_half_life: i64) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            ReputationCardError::UnauthorizedConfig
+        );
+        require!(score_half_life >= 0, ReputationCardError::InvalidHalfLife);
+
+        ctx.accounts.program_state.score_half_life = score_half_life;
+
+        msg!("Score half-life set to {} seconds", score_half_life);
+        Ok(())
+    }
+
     /// Create a reputation card for a recipient
     /// An issuer (verified user) can issue a reputation card to another user.
     /// 
@@ -34,20 +325,84 @@ pub mod reputation_card {
     /// * `card_type` - The type of reputation (e.g., Trustworthy, QualityProducts)
     /// * `message` - Optional message from the issuer
     /// * `rating` - Rating from 1-5
+    /// * `order_ref` - Optional completed marketplace order between issuer and recipient
     /// 
     /// # Security
     /// - Issuer must have a verified TrustToken
-    /// - Recipient must exist (have a wallet)
+    /// - Recipient must have a verified TrustToken too when `require_verified_recipient` is set
+    /// - Issuers cannot give cards to themselves
+    /// - One card per (issuer, recipient, card type) per cooldown window, and a daily cap per issuer
+    /// - An `order_ref` needs the marketplace authority's co-signature as purchase receipt,
+    ///   and backs at most one card (recorded in an `OrderRecord`)
     /// - Card is stored on-chain with immutable issuer/recipient
     pub fn create_card(
         ctx: Context<CreateCard>,
         card_type: CardType,
         message: String,
         rating: u8,
+        order_ref: Option<Pubkey>,
     ) -> Result<()> {
         // Validate inputs
-        require!(rating >= 1 && rating <= 5, ReputationCardError::InvalidRating);
+        require!((1..=5).contains(&rating), ReputationCardError::InvalidRating);
         require!(message.len() <= 500, ReputationCardError::MessageTooLong);
+        require!(
+            ctx.accounts.issuer.key() != ctx.accounts.recipient.key(),
+            ReputationCardError::SelfIssuedCard
+        );
+
+        // Security check: Issuer (and recipient, if required) must hold a verified TrustToken
+        let clock = Clock::get()?;
+        trust_token::require_verified(
+            &ctx.accounts.issuer_trust_token,
+            ctx.accounts.issuer.key(),
+            &clock,
+        )
+        .map_err(|_| error!(ReputationCardError::IssuerNotVerified))?;
+        if ctx.accounts.program_state.require_verified_recipient {
+            let recipient_trust_token = ctx
+                .accounts
+                .recipient_trust_token
+                .as_ref()
+                .ok_or(ReputationCardError::RecipientNotVerified)?;
+            trust_token::require_verified(
+                recipient_trust_token,
+                ctx.accounts.recipient.key(),
+                &clock,
+            )
+            .map_err(|_| error!(ReputationCardError::RecipientNotVerified))?;
+        }
+
+        // Security check: An order reference is only accepted with the marketplace's receipt
+        if order_ref.is_some() {
+            let marketplace_authority = ctx
+                .accounts
+                .marketplace_authority
+                .as_ref()
+                .ok_or(ReputationCardError::MissingPurchaseReceipt)?;
+            require!(
+                ctx.accounts.program_state.marketplace_authority
+                    == Some(marketplace_authority.key()),
+                ReputationCardError::InvalidPurchaseReceipt
+            );
+        }
+        require!(
+            order_ref.is_some() == ctx.accounts.order_record.is_some(),
+            ReputationCardError::MissingOrderRecord
+        );
+
+        // Anti-spam: cooldown per (issuer, recipient, card type)
+        let now = clock.unix_timestamp;
+        let program_state = &ctx.accounts.program_state;
+        let card_pair = &mut ctx.accounts.card_pair;
+        card_pair.record_issue(program_state.card_cooldown, now)?;
+        card_pair.issuer = ctx.accounts.issuer.key();
+        card_pair.recipient = ctx.accounts.recipient.key();
+        card_pair.card_type = card_type;
+
+        // Anti-spam: daily cap per issuer, counted per UTC day
+        let issuer_stats = &mut ctx.accounts.issuer_stats;
+        issuer_stats.record_issue(program_state.daily_card_cap, now)?;
+        issuer_stats.issuer = ctx.accounts.issuer.key();
 
         // Initialize the reputation card
         let card = &mut ctx.accounts.reputation_card;
@@ -57,9 +412,26 @@ pub mod reputation_card {
         card.message = message;
         card.rating = rating;
         card.status = CardStatus::Active;
-        card.issued_at = Clock::get()?.unix_timestamp;
+        card.issued_at = now;
         card.revoked_at = None;
         card.card_number = ctx.accounts.program_state.total_cards_issued;
+        card.order_ref = order_ref;
+        card.verified_purchase = order_ref.is_some();
+
+        // `init` on the order record rejects a second card for the same order
+        if let (Some(order_ref), Some(order_record)) =
+            (order_ref, ctx.accounts.order_record.as_mut())
+        {
+            order_record.order_ref = order_ref;
+            order_record.reputation_card = card.key();
+            order_record.recorded_at = now;
+        }
+
+        // Count the new card in the recipient's profile
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.recipient = card.recipient;
+        profile.add_card(card)?;
+        profile.updated_at = now;
 
         // Update program state
         let program_state = &mut ctx.accounts.program_state;
@@ -69,12 +441,13 @@ pub mod reputation_card {
             .ok_or(ReputationCardError::Overflow)?;
 
         msg!(
-            "Reputation card #{} created: {} → {} (Type: {:?}, Rating: {})",
+            "Reputation card #{} created: {} → {} (Type: {:?}, Rating: {}, Verified purchase: {})",
             card.card_number,
             card.issuer,
             card.recipient,
             card.card_type,
-            card.rating
+            card.rating,
+            card.verified_purchase
         );
 
         Ok(())
@@ -110,10 +483,15 @@ pub mod reputation_card {
             require!(r.len() <= 200, ReputationCardError::ReasonTooLong);
         }
 
-        // Update card status
+        // Update card status, moving it out of the recipient's active totals
+        let now = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
         card.status = CardStatus::Revoked;
-        card.revoked_at = Some(Clock::get()?.unix_timestamp);
+        card.revoked_at = Some(now);
         card.revocation_reason = reason;
+        profile.add_card(card)?;
+        profile.updated_at = now;
 
         // Update program state
         let program_state = &mut ctx.accounts.program_state;
@@ -136,6 +514,8 @@ pub mod reputation_card {
     /// # Security
     /// - Only the program authority can call this
     /// - Used for moderation and dispute resolution
+    /// - Cannot move a card into Disputed; that goes through dispute_card,
+    ///   which also sets the dispute deadline
     pub fn update_card_status(
         ctx: Context<UpdateCardStatus>,
         new_status: CardStatus,
@@ -145,10 +525,18 @@ pub mod reputation_card {
             ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
             ReputationCardError::UnauthorizedUpdate
         );
+        require!(
+            new_status != CardStatus::Disputed,
+            ReputationCardError::InvalidStatusUpdate
+        );
 
         let card = &mut ctx.accounts.reputation_card;
         let old_status = card.status;
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
         card.status = new_status;
+        profile.add_card(card)?;
+        profile.updated_at = Clock::get()?.unix_timestamp;
 
         msg!(
             "Card #{} status updated by authority: {:?} → {:?}",
@@ -184,10 +572,14 @@ pub mod reputation_card {
             ReputationCardError::CardNotRevoked
         );
 
-        // Update card status
+        // Update card status, counting it in the recipient's active totals again
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
         card.status = CardStatus::Active;
         card.revoked_at = None;
         card.revocation_reason = None;
+        profile.add_card(card)?;
+        profile.updated_at = Clock::get()?.unix_timestamp;
 
         // Update program state
         let program_state = &mut ctx.accounts.program_state;
@@ -200,6 +592,84 @@ pub mod reputation_card {
         Ok(())
     }
 
+    /// Amend a reputation card
+    /// The issuer can correct the rating and/or message shortly after issuing.
+    /// The replaced version is appended to the card's history account.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the amendment
+    /// * `rating` - New rating from 1-5, or `None` to keep the current one
+    /// * `message` - New message, or `None` to keep the current one
+    /// 
+    /// # Security
+    /// - Only the original issuer can amend their card
+    /// - Card must be in Active status and within `ReputationCard::AMEND_GRACE_PERIOD` of issuance
+    /// - Disputed cards can no longer be amended, so a moderator's decision stands
+    /// - At most `CardHistory::MAX_VERSIONS` amendments per card
+    pub fn amend_card(
+        ctx: Context<AmendCard>,
+        rating: Option<u8>,
+        message: Option<String>,
+    ) -> Result<()> {
+        let card = &mut ctx.accounts.reputation_card;
+
+        // Security check: Only issuer can amend
+        require!(
+            card.issuer == ctx.accounts.issuer.key(),
+            ReputationCardError::UnauthorizedAmend
+        );
+
+        // Check card is active, undisputed and still within the grace period
+        let now = Clock::get()?.unix_timestamp;
+        card.check_amendable(now)?;
+
+        // Validate inputs
+        require!(
+            rating.is_some() || message.is_some(),
+            ReputationCardError::EmptyAmendment
+        );
+        if let Some(rating) = rating {
+            require!((1..=5).contains(&rating), ReputationCardError::InvalidRating);
+        }
+        if let Some(ref m) = message {
+            require!(m.len() <= 500, ReputationCardError::MessageTooLong);
+        }
+
+        // Keep the version being replaced
+        let history = &mut ctx.accounts.card_history;
+        require!(
+            history.versions.len() < CardHistory::MAX_VERSIONS,
+            ReputationCardError::TooManyAmendments
+        );
+        history.card = card.key();
+        history.versions.push(CardVersion {
+            rating: card.rating,
+            message: card.message.clone(),
+            replaced_at: now,
+        });
+
+        // Apply the amendment, re-counting the card in the recipient's profile
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
+        if let Some(rating) = rating {
+            card.rating = rating;
+        }
+        if let Some(message) = message {
+            card.message = message;
+        }
+        card.amended_at = Some(now);
+        profile.add_card(card)?;
+        profile.updated_at = now;
+
+        msg!(
+            "Reputation card #{} amended by issuer (version {}, Rating: {})",
+            card.card_number,
+            history.versions.len() + 1,
+            card.rating
+        );
+        Ok(())
+    }
+
     /// Dispute a reputation card
     /// The recipient can dispute a card issued to them.
     /// 
@@ -209,7 +679,8 @@ pub mod reputation_card {
     /// 
     /// # Security
     /// - Only the recipient can dispute a card
-    /// - Card must be in Active status
+    /// - Card must be in Active status and not disputed before
+    /// - The dispute must be resolved within `dispute_window` or the card reverts to Active
     pub fn dispute_card(ctx: Context<DisputeCard>, dispute_reason: String) -> Result<()> {
         let card = &mut ctx.accounts.reputation_card;
 
@@ -225,98 +696,922 @@ pub mod reputation_card {
             ReputationCardError::CardNotActive
         );
 
+        // Each card gets one dispute; otherwise an expired dispute could be reopened forever
+        require!(
+            card.dispute_outcome.is_none(),
+            ReputationCardError::AlreadyDisputed
+        );
+
         // Validate dispute reason
         require!(
             dispute_reason.len() <= 500,
             ReputationCardError::DisputeReasonTooLong
         );
 
-        // Update card status
+        // Update card status, moving it from active to disputed in the recipient's profile
+        let now = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
         card.status = CardStatus::Disputed;
         card.dispute_reason = Some(dispute_reason);
+        card.disputed_at = Some(now);
+        card.dispute_deadline = Some(
+            now.checked_add(ctx.accounts.program_state.dispute_window)
+                .ok_or(ReputationCardError::Overflow)?,
+        );
+        profile.add_card(card)?;
+        profile.updated_at = now;
 
         msg!("Reputation card #{} disputed by recipient", card.card_number);
         Ok(())
     }
-}
 
-// ============================================================================
-// Account Structures
-// ============================================================================
+    /// Respond to a dispute
+    /// The issuer can add a counter-statement while the dispute is open.
+    /// Responding again replaces the previous statement.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the response
+    /// * `response` - The issuer's counter-statement
+    /// 
+    /// # Security
+    /// - Only the original issuer can respond
+    /// - Card must be Disputed and the dispute deadline not passed
+    pub fn respond_to_dispute(ctx: Context<RespondToDispute>, response: String) -> Result<()> {
+        let card = &mut ctx.accounts.reputation_card;
 
-/// Program state account that stores global configuration
-#[account]
-pub struct ProgramState {
-    /// The authority that can manage the program
-    pub authority: Pubkey,
-    /// Total number of reputation cards issued
-    pub total_cards_issued: u64,
-    /// Total number of cards revoked
-    pub total_cards_revoked: u64,
-}
+        // Security check: Only issuer can respond
+        require!(
+            card.issuer == ctx.accounts.issuer.key(),
+            ReputationCardError::UnauthorizedResponse
+        );
+        require!(
+            card.status == CardStatus::Disputed,
+            ReputationCardError::CardNotDisputed
+        );
+        require!(
+            !card.is_dispute_expired(Clock::get()?.unix_timestamp),
+            ReputationCardError::DisputeExpired
+        );
+        require!(
+            response.len() <= 500,
+            ReputationCardError::DisputeResponseTooLong
+        );
 
-impl ProgramState {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // authority
-        8 + // total_cards_issued
-        8; // total_cards_revoked
-}
+        card.dispute_response = Some(response);
 
-/// Reputation card account
-#[account]
-pub struct ReputationCard {
-    /// The user who issued this card
-    pub issuer: Pubkey,
-    /// The user who received this card
-    pub recipient: Pubkey,
-    /// Type of reputation
-    pub card_type: CardType,
-    /// Message from the issuer
-    pub message: String,
-    /// Rating (1-5)
-    pub rating: u8,
-    /// Current status of the card
-    pub status: CardStatus,
-    /// When the card was issued
-    pub issued_at: i64,
-    /// When the card was revoked (if applicable)
-    pub revoked_at: Option<i64>,
-    /// Reason for revocation (if applicable)
-    pub revocation_reason: Option<String>,
-    /// Reason for dispute (if applicable)
-    pub dispute_reason: Option<String>,
-    /// Unique card number
-    pub card_number: u64,
-}
+        msg!("Issuer responded to dispute on card #{}", card.card_number);
+        Ok(())
+    }
 
-impl ReputationCard {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // issuer
-        32 + // recipient
-        1 + // card_type (enum)
-        4 + 500 + // message (String with max 500 chars)
-        1 + // rating
-        1 + // status (enum)
-        8 + // issued_at
-        1 + 8 + // revoked_at (Option<i64>)
-        1 + 4 + 200 + // revocation_reason (Option<String> max 200)
-        1 + 4 + 500 + // dispute_reason (Option<String> max 500)
-        8; // card_number
-}
+    /// Resolve a dispute
+    /// - Upheld: the card stands and returns to Active
+    /// - Removed: the card is Suspended and no longer counts
+    /// - Amended: the card returns to Active with a corrected rating and/or message
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for resolution
+    /// * `outcome` - The decision (`Expired` is reserved for `expire_dispute`)
+    /// * `amended_rating` - New rating for `Amended`
+    /// * `amended_message` - New message for `Amended`
+    /// 
+    /// # Security
+    /// - Only the program authority or the dispute moderator can resolve disputes
+    /// - Card must be Disputed and the dispute deadline not passed
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        outcome: DisputeOutcome,
+        amended_rating: Option<u8>,
+        amended_message: Option<String>,
+    ) -> Result<()> {
+        // Security check: Only authority or moderator can resolve
+        let program_state = &ctx.accounts.program_state;
+        let moderator = ctx.accounts.moderator.key();
+        require!(
+            moderator == program_state.authority
+                || program_state.dispute_moderator == Some(moderator),
+            ReputationCardError::UnauthorizedResolve
+        );
 
-/// Types of reputation cards
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
-pub enum CardType {
-    Trustworthy,
-    QualityProducts,
-    FastShipping,
-    GoodCommunication,
+        let card = &mut ctx.accounts.reputation_card;
+        require!(
+            card.status == CardStatus::Disputed,
+            ReputationCardError::CardNotDisputed
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !card.is_dispute_expired(now),
+            ReputationCardError::DisputeExpired
+        );
+
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
+        match outcome {
+            DisputeOutcome::Upheld => card.status = CardStatus::Active,
+            DisputeOutcome::Removed => card.status = CardStatus::Suspended,
+            DisputeOutcome::Amended => {
+                require!(
+                    amended_rating.is_some() || amended_message.is_some(),
+                    ReputationCardError::InvalidDisputeOutcome
+                );
+                if let Some(rating) = amended_rating {
+                    require!((1..=5).contains(&rating), ReputationCardError::InvalidRating);
+                    card.rating = rating;
+                }
+                if let Some(message) = amended_message {
+                    require!(message.len() <= 500, ReputationCardError::MessageTooLong);
+                    card.message = message;
+                }
+                card.status = CardStatus::Active;
+            }
+            DisputeOutcome::Expired => {
+                return err!(ReputationCardError::InvalidDisputeOutcome);
+            }
+        }
+
+        card.dispute_outcome = Some(outcome);
+        card.dispute_resolved_at = Some(now);
+        card.dispute_resolved_by = Some(moderator);
+        profile.add_card(card)?;
+        profile.updated_at = now;
+
+        msg!(
+            "Dispute on card #{} resolved by {}: {:?}",
+            card.card_number,
+            moderator,
+            outcome
+        );
+        Ok(())
+    }
+
+    /// Close a dispute that passed its deadline unresolved
+    /// The card reverts to Active. Anyone can call this once the deadline has passed.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed to expire the dispute
+    /// 
+    /// # Security
+    /// - Card must be Disputed and its dispute deadline passed
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        let card = &mut ctx.accounts.reputation_card;
+        require!(
+            card.status == CardStatus::Disputed,
+            ReputationCardError::CardNotDisputed
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            card.is_dispute_expired(now),
+            ReputationCardError::DisputeStillOpen
+        );
+
+        let profile = &mut ctx.accounts.reputation_profile;
+        profile.remove_card(card)?;
+        card.status = CardStatus::Active;
+        card.dispute_outcome = Some(DisputeOutcome::Expired);
+        card.dispute_resolved_at = Some(now);
+        card.dispute_resolved_by = None;
+        profile.add_card(card)?;
+        profile.updated_at = now;
+
+        msg!("Dispute on card #{} expired; card reverted to Active", card.card_number);
+        Ok(())
+    }
+
+    /// Recompute a recipient's trust-weighted score, in batches
+    /// Each Active card is weighted by its issuer's verification level, TrustToken age
+    /// and own reputation, so cards from fresh or unverified wallets count for little.
+    /// Weights then decay by the card's age using the configured `score_half_life`.
+    /// Anyone can call this; the result only depends on on-chain state and the clock.
+    /// 
+    /// A recipient can hold more cards than fit in one transaction, so the sums are
+    /// staged in the profile: call with `start = true` for the first batch and
+    /// `start = false` for the rest. The score is committed once every Active card
+    /// has been counted. Any change to the recipient's cards in between invalidates
+    /// the staged sums, and the computation has to be started again.
+    /// 
+    /// Remaining accounts: for every card in the batch, in increasing `card_number`
+    /// order (continuing from the previous batch):
+    /// 1. the `ReputationCard`
+    /// 2. the issuer's identity chain (see `resolve_identity`): the issuer's trust_token
+    ///    `Identity` PDA and, if the identity migrated away, the links to its current wallet.
+    ///    Nothing further follows for the card if the chain ends without an identity.
+    /// 3. the TrustToken that identity links to
+    /// 4. the current wallet's `ReputationProfile` PDA (may be uninitialized)
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the recipient's profile and the card accounts
+    /// * `start` - Discard any staged sums and start a new computation with this batch
+    /// 
+    /// # Security
+    /// - Card numbers must strictly increase across all batches, so no card counts twice
+    /// - The score is only committed after `active_count` cards, i.e. every Active card
+    /// - Issuer accounts are checked against their PDA addresses, so callers cannot pick weights
+    /// - Cards issued from a wallet the issuer has since migrated away from keep their weight
+    /// - All batches are weighed as of the first batch's timestamp
+    pub fn recompute_score<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecomputeScore<'info>>,
+        start: bool,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.reputation_profile;
+
+        if start {
+            profile.pending_weighted_score = 0;
+            profile.pending_total_weight = 0;
+            profile.pending_cards = 0;
+            profile.pending_next_card = 0;
+            profile.pending_started_at = Clock::get()?.unix_timestamp;
+            profile.pending_revision = profile.revision;
+        } else {
+            require!(
+                profile.pending_started_at != 0 && profile.pending_revision == profile.revision,
+                ReputationCardError::StaleScoreBatch
+            );
+        }
+
+        let now = profile.pending_started_at;
+        let half_life = ctx.accounts.program_state.score_half_life;
+
+        let mut accounts = ctx.remaining_accounts.iter();
+        while let Some(card_info) = accounts.next() {
+            let card = Account::<ReputationCard>::try_from(card_info)?;
+
+            // Strictly increasing card numbers rule out passing a card twice
+            require!(
+                profile.counts_cards_of(&card.recipient)
+                    && card.status == CardStatus::Active
+                    && card.card_number >= profile.pending_next_card,
+                ReputationCardError::InvalidScoreAccounts
+            );
+            profile.pending_next_card = card
+                .card_number
+                .checked_add(1)
+                .ok_or(ReputationCardError::Overflow)?;
+
+            let weight = card_weight(&card, &mut accounts, now)?;
+            let weight = apply_decay(weight, now.saturating_sub(card.issued_at), half_life)?;
+            profile.pending_weighted_score = profile
+                .pending_weighted_score
+                .checked_add(
+                    (card.rating as u64)
+                        .checked_mul(weight)
+                        .ok_or(ReputationCardError::Overflow)?,
+                )
+                .ok_or(ReputationCardError::Overflow)?;
+            profile.pending_total_weight = profile
+                .pending_total_weight
+                .checked_add(weight)
+                .ok_or(ReputationCardError::Overflow)?;
+            profile.pending_cards = profile
+                .pending_cards
+                .checked_add(1)
+                .ok_or(ReputationCardError::Overflow)?;
+        }
+        require!(
+            profile.pending_cards <= profile.active_count,
+            ReputationCardError::InvalidScoreAccounts
+        );
+
+        if profile.pending_cards < profile.active_count {
+            msg!(
+                "Weighted score for {}: {}/{} cards counted",
+                profile.recipient,
+                profile.pending_cards,
+                profile.active_count
+            );
+            return Ok(());
+        }
+
+        profile.weighted_score = profile.pending_weighted_score;
+        profile.total_weight = profile.pending_total_weight;
+        profile.weighted_rating = profile.blended_rating()?;
+        profile.score_updated_at = now;
+        profile.pending_started_at = 0;
+
+        msg!(
+            "Weighted rating for {}: {} ({} over weight {}, {} cards)",
+            profile.recipient,
+            profile.weighted_rating,
+            profile.weighted_score,
+            profile.total_weight,
+            profile.active_count
+        );
+        Ok(())
+    }
+
+    /// Bring a recipient's weighted rating up to date with the clock
+    /// Decays the stored score and weight by the time elapsed since they were last
+    /// computed, so the rating drifts back towards `ReputationProfile::PRIOR_RATING`
+    /// while the recipient receives no new cards. Because decay is exponential the
+    /// sums match a full `recompute_score`, apart from changes in issuer standing.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the recipient's profile
+    /// 
+    /// # Security
+    /// - Permissionless; the result only depends on stored state and the clock
+    pub fn refresh_score(ctx: Context<RefreshScore>) -> Result<()> {
+        let half_life = ctx.accounts.program_state.score_half_life;
+        let profile = &mut ctx.accounts.reputation_profile;
+        let now = Clock::get()?.unix_timestamp;
+        let elapsed = now.saturating_sub(profile.score_updated_at);
+
+        profile.weighted_score = apply_decay(profile.weighted_score, elapsed, half_life)?;
+        profile.total_weight = apply_decay(profile.total_weight, elapsed, half_life)?;
+        profile.weighted_rating = profile.blended_rating()?;
+        profile.score_updated_at = now;
+
+        msg!(
+            "Weighted rating for {} decayed over {} seconds: {} ({} over weight {})",
+            profile.recipient,
+            elapsed,
+            profile.weighted_rating,
+            profile.weighted_score,
+            profile.total_weight
+        );
+        Ok(())
+    }
+
+    /// Move a migrated wallet's reputation to the wallet now holding its identity
+    /// Cards stay keyed by the wallet they were given to, so after trust_token migrates
+    /// an identity the old wallet's profile still holds them. This merges that profile
+    /// into the current wallet's, which counts the old wallet's cards from then on:
+    /// card updates are applied to it and `recompute_score` accepts them.
+    /// 
+    /// Remaining accounts: the identity chain from the old wallet to the current one
+    /// (see `resolve_identity`), following at least one `IdentityLink`.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing both profiles
+    /// 
+    /// # Security
+    /// - Permissionless: the destination is read from trust_token's identity accounts
+    /// - Each profile is moved once, so every card stays counted in exactly one profile
+    pub fn migrate_profile<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateProfile<'info>>,
+    ) -> Result<()> {
+        let old_profile = &mut ctx.accounts.old_profile;
+        let new_owner = ctx.accounts.new_owner.key();
+
+        // Security check: The old wallet's identity must have moved to `new_owner`
+        let mut accounts = ctx.remaining_accounts.iter();
+        let (identity, links) = resolve_identity(old_profile.recipient, &mut accounts)?
+            .ok_or(ReputationCardError::InvalidIdentityChain)?;
+        require!(
+            links > 0 && identity.owner == new_owner && accounts.next().is_none(),
+            ReputationCardError::InvalidIdentityChain
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let new_profile = &mut ctx.accounts.new_profile;
+        new_profile.recipient = new_owner;
+        new_profile.absorb(old_profile, now, ctx.accounts.program_state.score_half_life)?;
+
+        msg!(
+            "Reputation profile of {} moved to {} ({} active cards)",
+            old_profile.recipient,
+            new_owner,
+            new_profile.active_count
+        );
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+/// Program state account that stores global configuration
+#[account]
+pub struct ProgramState {
+    /// The authority that can manage the program
+    pub authority: Pubkey,
+    /// Total number of reputation cards issued
+    pub total_cards_issued: u64,
+    /// Total number of cards revoked
+    pub total_cards_revoked: u64,
+    /// Proposed successor authority awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+    /// Whether card recipients must also hold a verified TrustToken
+    pub require_verified_recipient: bool,
+    /// Seconds before an issuer can give the same recipient another card of the same type
+    pub card_cooldown: i64,
+    /// Maximum cards one issuer can create per day (0 = unlimited)
+    pub daily_card_cap: u32,
+    /// Seconds for a card's score weight to halve (0 = no decay)
+    pub score_half_life: i64,
+    /// Marketplace key whose co-signature proves a card's `order_ref`
+    pub marketplace_authority: Option<Pubkey>,
+    /// Seconds a dispute stays open before the card reverts to Active
+    pub dispute_window: i64,
+    /// Key that can resolve disputes besides the authority
+    pub dispute_moderator: Option<Pubkey>,
+}
+
+impl ProgramState {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // total_cards_issued
+        8 + // total_cards_revoked
+        1 + 32 + // pending_authority (Option<Pubkey>)
+        1 + // require_verified_recipient
+        8 + // card_cooldown
+        4 + // daily_card_cap
+        8 + // score_half_life
+        1 + 32 + // marketplace_authority (Option<Pubkey>)
+        8 + // dispute_window
+        1 + 32; // dispute_moderator (Option<Pubkey>)
+
+    /// Default cooldown per (issuer, recipient, card type): 30 days
+    pub const DEFAULT_CARD_COOLDOWN: i64 = 30 * 24 * 60 * 60;
+    /// Default per-issuer daily cap
+    pub const DEFAULT_DAILY_CARD_CAP: u32 = 10;
+    /// Default score half-life: 180 days
+    pub const DEFAULT_SCORE_HALF_LIFE: i64 = 180 * 24 * 60 * 60;
+    /// Default dispute window: 14 days
+    pub const DEFAULT_DISPUTE_WINDOW: i64 = 14 * 24 * 60 * 60;
+}
+
+/// Last card of one type an issuer gave a recipient, used for the cooldown
+#[account]
+pub struct CardPairRecord {
+    /// The issuer
+    pub issuer: Pubkey,
+    /// The recipient
+    pub recipient: Pubkey,
+    /// The card type this record tracks
+    pub card_type: CardType,
+    /// When the last card of this type was issued
+    pub last_issued_at: i64,
+}
+
+impl CardPairRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // issuer
+        32 + // recipient
+        1 + // card_type (enum)
+        8; // last_issued_at
+
+    /// Record a card issued at `now`, failing if the last one is within `cooldown`
+    pub fn record_issue(&mut self, cooldown: i64, now: i64) -> Result<()> {
+        if self.last_issued_at > 0 {
+            let next_allowed = self
+                .last_issued_at
+                .checked_add(cooldown)
+                .ok_or(ReputationCardError::Overflow)?;
+            require!(now >= next_allowed, ReputationCardError::CardCooldownActive);
+        }
+        self.last_issued_at = now;
+        Ok(())
+    }
+}
+
+/// Marketplace order that already backs a card
+/// Created with `init` by `create_card`, so each order can back only one card.
+#[account]
+pub struct OrderRecord {
+    /// The marketplace order reference
+    pub order_ref: Pubkey,
+    /// The card the order backs
+    pub reputation_card: Pubkey,
+    /// When the card was issued
+    pub recorded_at: i64,
+}
+
+impl OrderRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // order_ref
+        32 + // reputation_card
+        8; // recorded_at
+}
+
+/// Per-issuer issuance counter, used for the daily cap
+#[account]
+pub struct IssuerStats {
+    /// The issuer
+    pub issuer: Pubkey,
+    /// Day index (unix time / 86400) that `cards_today` counts
+    pub day: i64,
+    /// Cards issued during `day`
+    pub cards_today: u32,
+}
+
+impl IssuerStats {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // issuer
+        8 + // day
+        4; // cards_today
+
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    /// Count a card issued at `now`, failing once `daily_cap` cards were issued that UTC day
+    /// A `daily_cap` of 0 means unlimited.
+    pub fn record_issue(&mut self, daily_cap: u32, now: i64) -> Result<()> {
+        let today = now / Self::SECONDS_PER_DAY;
+        if self.day != today {
+            self.day = today;
+            self.cards_today = 0;
+        }
+        require!(
+            daily_cap == 0 || self.cards_today < daily_cap,
+            ReputationCardError::DailyCardCapReached
+        );
+        self.cards_today = self
+            .cards_today
+            .checked_add(1)
+            .ok_or(ReputationCardError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Reputation card account
+#[account]
+pub struct ReputationCard {
+    /// The user who issued this card
+    pub issuer: Pubkey,
+    /// The user who received this card
+    pub recipient: Pubkey,
+    /// Type of reputation
+    pub card_type: CardType,
+    /// Message from the issuer
+    pub message: String,
+    /// Rating (1-5)
+    pub rating: u8,
+    /// Current status of the card
+    pub status: CardStatus,
+    /// When the card was issued
+    pub issued_at: i64,
+    /// When the card was revoked (if applicable)
+    pub revoked_at: Option<i64>,
+    /// Reason for revocation (if applicable)
+    pub revocation_reason: Option<String>,
+    /// Reason for dispute (if applicable)
+    pub dispute_reason: Option<String>,
+    /// Unique card number
+    pub card_number: u64,
+    /// Marketplace order this card reviews (if any)
+    pub order_ref: Option<Pubkey>,
+    /// Whether the marketplace confirmed a completed purchase behind this card
+    pub verified_purchase: bool,
+    /// When the card was disputed (if applicable)
+    pub disputed_at: Option<i64>,
+    /// When an unresolved dispute reverts the card to Active
+    pub dispute_deadline: Option<i64>,
+    /// Issuer's counter-statement to the dispute
+    pub dispute_response: Option<String>,
+    /// How the dispute ended
+    pub dispute_outcome: Option<DisputeOutcome>,
+    /// When the dispute was resolved or expired
+    pub dispute_resolved_at: Option<i64>,
+    /// Who resolved the dispute (None if it expired)
+    pub dispute_resolved_by: Option<Pubkey>,
+    /// When the issuer last amended the card (if applicable)
+    pub amended_at: Option<i64>,
+}
+
+impl ReputationCard {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // issuer
+        32 + // recipient
+        1 + // card_type (enum)
+        4 + 500 + // message (String with max 500 chars)
+        1 + // rating
+        1 + // status (enum)
+        8 + // issued_at
+        1 + 8 + // revoked_at (Option<i64>)
+        1 + 4 + 200 + // revocation_reason (Option<String> max 200)
+        1 + 4 + 500 + // dispute_reason (Option<String> max 500)
+        8 + // card_number
+        1 + 32 + // order_ref (Option<Pubkey>)
+        1 + // verified_purchase
+        1 + 8 + // disputed_at (Option<i64>)
+        1 + 8 + // dispute_deadline (Option<i64>)
+        1 + 4 + 500 + // dispute_response (Option<String> max 500)
+        1 + 1 + // dispute_outcome (Option<enum>)
+        1 + 8 + // dispute_resolved_at (Option<i64>)
+        1 + 32 + // dispute_resolved_by (Option<Pubkey>)
+        1 + 8; // amended_at (Option<i64>)
+
+    /// How long after issuance the issuer can amend a card: 7 days
+    pub const AMEND_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+    /// Whether an open dispute has passed its deadline
+    pub fn is_dispute_expired(&self, now: i64) -> bool {
+        self.dispute_deadline.is_some_and(|deadline| now >= deadline)
+    }
+
+    /// Check the issuer can still amend the card at `now`
+    /// The card must be Active, never disputed (an expired or resolved dispute
+    /// also returns the card to Active) and within `AMEND_GRACE_PERIOD` of issuance.
+    pub fn check_amendable(&self, now: i64) -> Result<()> {
+        require!(
+            self.status == CardStatus::Active,
+            ReputationCardError::CardNotActive
+        );
+        require!(
+            self.disputed_at.is_none() && self.dispute_outcome.is_none(),
+            ReputationCardError::CardDisputed
+        );
+        let deadline = self
+            .issued_at
+            .checked_add(Self::AMEND_GRACE_PERIOD)
+            .ok_or(ReputationCardError::Overflow)?;
+        require!(now <= deadline, ReputationCardError::AmendWindowClosed);
+        Ok(())
+    }
+}
+
+/// Previous versions of an amended reputation card
+#[account]
+pub struct CardHistory {
+    /// The card these versions belong to
+    pub card: Pubkey,
+    /// Replaced versions, oldest first
+    pub versions: Vec<CardVersion>,
+}
+
+impl CardHistory {
+    /// Maximum amendments kept per card
+    pub const MAX_VERSIONS: usize = 5;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // card
+        4 + Self::MAX_VERSIONS * CardVersion::LEN; // versions (Vec<CardVersion>)
+}
+
+/// A replaced rating and message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CardVersion {
+    /// Rating before the amendment
+    pub rating: u8,
+    /// Message before the amendment
+    pub message: String,
+    /// When this version was replaced
+    pub replaced_at: i64,
+}
+
+impl CardVersion {
+    pub const LEN: usize = 1 + // rating
+        4 + 500 + // message (String with max 500 chars)
+        8; // replaced_at
+}
+
+/// Aggregated reputation of one recipient
+/// Kept in step with every card status change, so other programs can read a
+/// recipient's totals without scanning their cards. Only Active cards count
+/// towards the per-type counts and the rating sum.
+#[account]
+pub struct ReputationProfile {
+    /// The recipient this profile belongs to
+    pub recipient: Pubkey,
+    /// Active cards per `CardType`, indexed by the enum's discriminant
+    pub card_type_counts: [u32; CardType::COUNT],
+    /// Sum of the ratings of Active cards
+    pub rating_sum: u64,
+    /// Number of Active cards
+    pub active_count: u32,
+    /// Number of Disputed cards
+    pub disputed_count: u32,
+    /// When the profile last changed
+    pub updated_at: i64,
+    /// Sum of rating × weight over Active cards, as of the last `recompute_score`
+    /// (weights in basis points, `ReputationProfile::WEIGHT_ONE` = 1.0)
+    pub weighted_score: u64,
+    /// Sum of card weights in basis points
+    pub total_weight: u64,
+    /// When the weighted score was last computed or decayed
+    pub score_updated_at: i64,
+    /// Bumped on every change to the recipient's cards, invalidating staged score batches
+    pub revision: u64,
+    /// Staged `weighted_score` of a `recompute_score` in progress
+    pub pending_weighted_score: u64,
+    /// Staged `total_weight` of a `recompute_score` in progress
+    pub pending_total_weight: u64,
+    /// Cards counted so far by the `recompute_score` in progress
+    pub pending_cards: u32,
+    /// Lowest card number the next batch may contain
+    pub pending_next_card: u64,
+    /// When the `recompute_score` in progress started (0 if none)
+    pub pending_started_at: i64,
+    /// `revision` when the `recompute_score` in progress started
+    pub pending_revision: u64,
+    /// Weighted average rating blended with the prior, × `WEIGHT_ONE` (see `blended_rating`)
+    pub weighted_rating: u64,
+    /// The wallet this profile was merged into after its identity migrated
+    pub moved_to: Option<Pubkey>,
+    /// Migrated wallets whose cards this profile counts (see `migrate_profile`)
+    pub merged_from: Vec<Pubkey>,
+}
+
+impl ReputationProfile {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // recipient
+        4 * CardType::COUNT + // card_type_counts
+        8 + // rating_sum
+        4 + // active_count
+        4 + // disputed_count
+        8 + // updated_at
+        8 + // weighted_score
+        8 + // total_weight
+        8 + // score_updated_at
+        8 + // revision
+        8 + // pending_weighted_score
+        8 + // pending_total_weight
+        4 + // pending_cards
+        8 + // pending_next_card
+        8 + // pending_started_at
+        8 + // pending_revision
+        8 + // weighted_rating
+        1 + 32 + // moved_to (Option<Pubkey>)
+        4 + Self::MAX_MERGED * 32; // merged_from (Vec<Pubkey>)
+
+    /// Maximum number of migrated wallets a profile can count cards for
+    pub const MAX_MERGED: usize = 8;
+
+    /// A weight of 1.0 in basis points
+    pub const WEIGHT_ONE: u64 = 10_000;
+    /// TrustToken age at which an issuer's age weight stops growing (1 year)
+    pub const MATURE_TOKEN_AGE: i64 = 365 * 24 * 60 * 60;
+    /// Active cards at which an issuer's reputation weight stops growing
+    pub const MATURE_ACTIVE_CARDS: u64 = 20;
+    /// Weight multiplier for cards backed by a verified purchase (1.5)
+    pub const VERIFIED_PURCHASE_WEIGHT: u64 = 15_000;
+    /// Rating a recipient without (recent) cards is assumed to have
+    pub const PRIOR_RATING: u64 = 3;
+    /// Weight of the prior, as if it were one card from a mature Basic issuer
+    pub const PRIOR_WEIGHT: u64 = Self::WEIGHT_ONE;
+
+    /// Derive the profile address for a recipient
+    pub fn address(recipient: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"reputation_profile", recipient.as_ref()], &crate::ID)
+    }
+
+    /// Weighted average rating blended with `PRIOR_RATING`, × `WEIGHT_ONE`
+    /// (weighted_score + PRIOR_RATING × PRIOR_WEIGHT) / (total_weight + PRIOR_WEIGHT)
+    /// 
+    /// Decay shrinks the card weights but not the prior's, so old cards matter less
+    /// and less and the rating returns to the prior unless new cards keep it up.
+    pub fn blended_rating(&self) -> Result<u64> {
+        let prior = (Self::PRIOR_RATING * Self::PRIOR_WEIGHT) as u128;
+        let numerator = (self.weighted_score as u128 + prior) * Self::WEIGHT_ONE as u128;
+        let denominator = self.total_weight as u128 + Self::PRIOR_WEIGHT as u128;
+        u64::try_from(numerator / denominator).map_err(|_| error!(ReputationCardError::Overflow))
+    }
+
+    /// Whether this profile counts the cards received by `recipient`
+    /// A profile counts its own recipient's cards until it is moved, and those of
+    /// every wallet merged into it. Each recipient is counted by exactly one profile.
+    pub fn counts_cards_of(&self, recipient: &Pubkey) -> bool {
+        (self.recipient == *recipient && self.moved_to.is_none())
+            || self.merged_from.contains(recipient)
+    }
+
+    /// Merge `other` into this profile, leaving `other` empty and pointing here
+    /// The counts are added; the weighted sums are decayed to `now` and added.
+    pub fn absorb(&mut self, other: &mut ReputationProfile, now: i64, half_life: i64) -> Result<()> {
+        // A moved profile only becomes live again when its own cards come back with `other`
+        require!(
+            self.moved_to.is_none() || other.merged_from.contains(&self.recipient),
+            ReputationCardError::ProfileMoved
+        );
+
+        for (count, other_count) in self.card_type_counts.iter_mut().zip(other.card_type_counts) {
+            *count = count.checked_add(other_count).ok_or(ReputationCardError::Overflow)?;
+        }
+        self.rating_sum = self
+            .rating_sum
+            .checked_add(other.rating_sum)
+            .ok_or(ReputationCardError::Overflow)?;
+        self.active_count = self
+            .active_count
+            .checked_add(other.active_count)
+            .ok_or(ReputationCardError::Overflow)?;
+        self.disputed_count = self
+            .disputed_count
+            .checked_add(other.disputed_count)
+            .ok_or(ReputationCardError::Overflow)?;
+
+        let weighted_score =
+            apply_decay(self.weighted_score, now.saturating_sub(self.score_updated_at), half_life)?;
+        let other_weighted_score =
+            apply_decay(other.weighted_score, now.saturating_sub(other.score_updated_at), half_life)?;
+        let total_weight =
+            apply_decay(self.total_weight, now.saturating_sub(self.score_updated_at), half_life)?;
+        let other_total_weight =
+            apply_decay(other.total_weight, now.saturating_sub(other.score_updated_at), half_life)?;
+        self.weighted_score = weighted_score
+            .checked_add(other_weighted_score)
+            .ok_or(ReputationCardError::Overflow)?;
+        self.total_weight = total_weight
+            .checked_add(other_total_weight)
+            .ok_or(ReputationCardError::Overflow)?;
+        self.weighted_rating = self.blended_rating()?;
+        self.score_updated_at = now;
+
+        let merged = std::iter::once(other.recipient).chain(other.merged_from.drain(..));
+        for wallet in merged {
+            if wallet != self.recipient && !self.merged_from.contains(&wallet) {
+                self.merged_from.push(wallet);
+            }
+        }
+        self.merged_from.retain(|wallet| *wallet != self.recipient);
+        require!(
+            self.merged_from.len() <= Self::MAX_MERGED,
+            ReputationCardError::TooManyMergedProfiles
+        );
+        self.moved_to = None;
+        self.revision = self.revision.wrapping_add(1);
+        self.pending_started_at = 0;
+        self.updated_at = now;
+
+        other.card_type_counts = [0; CardType::COUNT];
+        other.rating_sum = 0;
+        other.active_count = 0;
+        other.disputed_count = 0;
+        other.weighted_score = 0;
+        other.total_weight = 0;
+        other.weighted_rating = other.blended_rating()?;
+        other.score_updated_at = now;
+        other.moved_to = Some(self.recipient);
+        other.revision = other.revision.wrapping_add(1);
+        other.pending_started_at = 0;
+        other.updated_at = now;
+        Ok(())
+    }
+
+    /// Add a card's contribution for its current status
+    pub fn add_card(&mut self, card: &ReputationCard) -> Result<()> {
+        self.revision = self.revision.wrapping_add(1);
+        match card.status {
+            CardStatus::Active => {
+                let count = &mut self.card_type_counts[card.card_type as usize];
+                *count = count.checked_add(1).ok_or(ReputationCardError::Overflow)?;
+                self.rating_sum = self
+                    .rating_sum
+                    .checked_add(card.rating as u64)
+                    .ok_or(ReputationCardError::Overflow)?;
+                self.active_count = self
+                    .active_count
+                    .checked_add(1)
+                    .ok_or(ReputationCardError::Overflow)?;
+            }
+            CardStatus::Disputed => {
+                self.disputed_count = self
+                    .disputed_count
+                    .checked_add(1)
+                    .ok_or(ReputationCardError::Overflow)?;
+            }
+            CardStatus::Revoked | CardStatus::Suspended => {}
+        }
+        Ok(())
+    }
+
+    /// Remove a card's contribution for its current status
+    pub fn remove_card(&mut self, card: &ReputationCard) -> Result<()> {
+        self.revision = self.revision.wrapping_add(1);
+        match card.status {
+            CardStatus::Active => {
+                let count = &mut self.card_type_counts[card.card_type as usize];
+                *count = count.checked_sub(1).ok_or(ReputationCardError::Underflow)?;
+                self.rating_sum = self
+                    .rating_sum
+                    .checked_sub(card.rating as u64)
+                    .ok_or(ReputationCardError::Underflow)?;
+                self.active_count = self
+                    .active_count
+                    .checked_sub(1)
+                    .ok_or(ReputationCardError::Underflow)?;
+            }
+            CardStatus::Disputed => {
+                self.disputed_count = self
+                    .disputed_count
+                    .checked_sub(1)
+                    .ok_or(ReputationCardError::Underflow)?;
+            }
+            CardStatus::Revoked | CardStatus::Suspended => {}
+        }
+        Ok(())
+    }
+}
+
+/// Types of reputation cards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CardType {
+    Trustworthy,
+    QualityProducts,
+    FastShipping,
+    GoodCommunication,
     FairPricing,
     Reliable,
     Professional,
     Responsive,
 }
 
+impl CardType {
+    /// Number of card types
+    pub const COUNT: usize = 8;
+}
+
 /// Status of a reputation card
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CardStatus {
@@ -326,6 +1621,19 @@ pub enum CardStatus {
     Suspended,
 }
 
+/// Result of a card dispute
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeOutcome {
+    /// The card stands as issued
+    Upheld,
+    /// The card was taken down
+    Removed,
+    /// The card stands with a corrected rating and/or message
+    Amended,
+    /// Nobody resolved the dispute before its deadline
+    Expired,
+}
+
 // ============================================================================
 // Context Structures
 // ============================================================================
@@ -345,63 +1653,439 @@ pub struct Initialize<'info> {
         seeds = [b"program_state"],
         bump
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub program_state: Account<'info, ProgramState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for proposing a new authority
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    /// The current program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for accepting a pending authority transfer
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// The proposed authority
+    pub new_authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for migrating the program state account
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// The program authority (pays for the extra space)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account in its old layout
+    /// CHECK: Cannot be deserialized before migration; discriminator and authority are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub program_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating a reputation card
+#[derive(Accounts)]
+pub struct MigrateCard<'info> {
+    /// Whoever pays for the extra space and the profile
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The card's recipient
+    /// CHECK: Compared against the recipient stored in the card
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Reputation card in its old layout
+    /// CHECK: Cannot be deserialized before migration; discriminator and recipient are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub reputation_card: UncheckedAccount<'info>,
+
+    /// The recipient's aggregated reputation profile, which must not have been moved
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ReputationProfile::LEN,
+        seeds = [b"reputation_profile", recipient.key().as_ref()],
+        bump,
+        constraint = reputation_profile.moved_to.is_none() @ ReputationCardError::ProfileMoved,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for changing the recipient verification requirement
+#[derive(Accounts)]
+pub struct SetRequireVerifiedRecipient<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for changing the marketplace receipt signer
+#[derive(Accounts)]
+pub struct SetMarketplaceAuthority<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for changing dispute configuration
+#[derive(Accounts)]
+pub struct SetDisputeConfig<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for changing issuance limits
+#[derive(Accounts)]
+pub struct SetIssuanceLimits<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for changing the score half-life
+#[derive(Accounts)]
+pub struct SetScoreHalfLife<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for creating a reputation card
+#[derive(Accounts)]
+#[instruction(card_type: CardType, message: String, rating: u8, order_ref: Option<Pubkey>)]
+pub struct CreateCard<'info> {
+    /// The user issuing the card (must be verified)
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    /// The issuer's TrustToken, owned by the trust_token program
+    #[account(
+        seeds = [b"trust_token", issuer_trust_token.mint.as_ref()],
+        bump,
+        seeds::program = trust_token::ID,
+    )]
+    pub issuer_trust_token: Account<'info, TrustToken>,
+
+    /// The recipient of the card
+    /// CHECK: We only need their public key
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The recipient's TrustToken, required when `require_verified_recipient` is set
+    #[account(
+        seeds = [b"trust_token", recipient_trust_token.mint.as_ref()],
+        bump,
+        seeds::program = trust_token::ID,
+    )]
+    pub recipient_trust_token: Option<Account<'info, TrustToken>>,
+
+    /// The marketplace authority, co-signing as purchase receipt when `order_ref` is set
+    pub marketplace_authority: Option<Signer<'info>>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The reputation card account
+    #[account(
+        init,
+        payer = issuer,
+        space = ReputationCard::LEN,
+        seeds = [
+            b"reputation_card",
+            issuer.key().as_ref(),
+            recipient.key().as_ref(),
+            &program_state.total_cards_issued.to_le_bytes()
+        ],
+        bump
+    )]
+    pub reputation_card: Account<'info, ReputationCard>,
+
+    /// Cooldown record for this (issuer, recipient, card type)
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = CardPairRecord::LEN,
+        seeds = [
+            b"card_pair",
+            issuer.key().as_ref(),
+            recipient.key().as_ref(),
+            &[card_type as u8]
+        ],
+        bump
+    )]
+    pub card_pair: Account<'info, CardPairRecord>,
+
+    /// Marks `order_ref` as used, required when an `order_ref` is given
+    #[account(
+        init,
+        payer = issuer,
+        space = OrderRecord::LEN,
+        seeds = [b"order_ref", order_ref.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub order_record: Option<Account<'info, OrderRecord>>,
+
+    /// The issuer's daily issuance counter
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = IssuerStats::LEN,
+        seeds = [b"issuer_stats", issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_stats: Account<'info, IssuerStats>,
+
+    /// The recipient's aggregated reputation profile, which must not have been moved
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = ReputationProfile::LEN,
+        seeds = [b"reputation_profile", recipient.key().as_ref()],
+        bump,
+        constraint = reputation_profile.moved_to.is_none() @ ReputationCardError::ProfileMoved,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for revoking a card
+#[derive(Accounts)]
+pub struct RevokeCard<'info> {
+    /// The issuer who is revoking the card
+    pub issuer: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The reputation card to revoke
+    #[account(
+        mut,
+        seeds = [
+            b"reputation_card",
+            reputation_card.issuer.as_ref(),
+            reputation_card.recipient.as_ref(),
+            &reputation_card.card_number.to_le_bytes()
+        ],
+        bump
+    )]
+    pub reputation_card: Account<'info, ReputationCard>,
+
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+}
+
+/// Context for updating card status (admin)
+#[derive(Accounts)]
+pub struct UpdateCardStatus<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The reputation card to update
+    #[account(
+        mut,
+        seeds = [
+            b"reputation_card",
+            reputation_card.issuer.as_ref(),
+            reputation_card.recipient.as_ref(),
+            &reputation_card.card_number.to_le_bytes()
+        ],
+        bump
+    )]
+    pub reputation_card: Account<'info, ReputationCard>,
+
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+}
+
+/// Context for restoring a card
+#[derive(Accounts)]
+pub struct RestoreCard<'info> {
+    /// The issuer who is restoring the card
+    pub issuer: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The reputation card to restore
+    #[account(
+        mut,
+        seeds = [
+            b"reputation_card",
+            reputation_card.issuer.as_ref(),
+            reputation_card.recipient.as_ref(),
+            &reputation_card.card_number.to_le_bytes()
+        ],
+        bump
+    )]
+    pub reputation_card: Account<'info, ReputationCard>,
 
-    pub system_program: Program<'info, System>,
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
 }
 
-/// Context for creating a reputation card
+/// Context for amending a card
 #[derive(Accounts)]
-pub struct CreateCard<'info> {
-    /// The user issuing the card (must be verified)
+pub struct AmendCard<'info> {
+    /// The issuer who is amending the card
     #[account(mut)]
     pub issuer: Signer<'info>,
 
-    /// The recipient of the card
-    /// CHECK: We only need their public key
-    pub recipient: UncheckedAccount<'info>,
-
-    /// Program state account
+    /// The reputation card to amend
     #[account(
         mut,
-        seeds = [b"program_state"],
+        seeds = [
+            b"reputation_card",
+            reputation_card.issuer.as_ref(),
+            reputation_card.recipient.as_ref(),
+            &reputation_card.card_number.to_le_bytes()
+        ],
         bump
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub reputation_card: Account<'info, ReputationCard>,
 
-    /// The reputation card account
+    /// Previous versions of the card, created on first amendment
     #[account(
-        init,
+        init_if_needed,
         payer = issuer,
-        space = ReputationCard::LEN,
-        seeds = [
-            b"reputation_card",
-            issuer.key().as_ref(),
-            recipient.key().as_ref(),
-            &program_state.total_cards_issued.to_le_bytes()
-        ],
+        space = CardHistory::LEN,
+        seeds = [b"card_history", reputation_card.key().as_ref()],
         bump
     )]
-    pub reputation_card: Account<'info, ReputationCard>,
+    pub card_history: Account<'info, CardHistory>,
+
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
 
     pub system_program: Program<'info, System>,
 }
 
-/// Context for revoking a card
+/// Context for disputing a card
 #[derive(Accounts)]
-pub struct RevokeCard<'info> {
-    /// The issuer who is revoking the card
-    pub issuer: Signer<'info>,
+pub struct DisputeCard<'info> {
+    /// The recipient who is disputing the card
+    pub recipient: Signer<'info>,
 
-    /// Program state account
+    /// Program state account (dispute window)
     #[account(
-        mut,
         seeds = [b"program_state"],
         bump
     )]
     pub program_state: Account<'info, ProgramState>,
 
-    /// The reputation card to revoke
+    /// The reputation card to dispute
     #[account(
         mut,
         seeds = [
@@ -413,22 +2097,25 @@ pub struct RevokeCard<'info> {
         bump
     )]
     pub reputation_card: Account<'info, ReputationCard>,
-}
-
-/// Context for updating card status (admin)
-#[derive(Accounts)]
-pub struct UpdateCardStatus<'info> {
-    /// The program authority
-    pub authority: Signer<'info>,
 
-    /// Program state account
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
     #[account(
-        seeds = [b"program_state"],
-        bump
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub reputation_profile: Account<'info, ReputationProfile>,
+}
 
-    /// The reputation card to update
+/// Context for responding to a dispute
+#[derive(Accounts)]
+pub struct RespondToDispute<'info> {
+    /// The issuer of the disputed card
+    pub issuer: Signer<'info>,
+
+    /// The disputed reputation card
     #[account(
         mut,
         seeds = [
@@ -442,21 +2129,20 @@ pub struct UpdateCardStatus<'info> {
     pub reputation_card: Account<'info, ReputationCard>,
 }
 
-/// Context for restoring a card
+/// Context for resolving a dispute
 #[derive(Accounts)]
-pub struct RestoreCard<'info> {
-    /// The issuer who is restoring the card
-    pub issuer: Signer<'info>,
+pub struct ResolveDispute<'info> {
+    /// The program authority or dispute moderator
+    pub moderator: Signer<'info>,
 
     /// Program state account
     #[account(
-        mut,
         seeds = [b"program_state"],
         bump
     )]
     pub program_state: Account<'info, ProgramState>,
 
-    /// The reputation card to restore
+    /// The disputed reputation card
     #[account(
         mut,
         seeds = [
@@ -468,15 +2154,22 @@ pub struct RestoreCard<'info> {
         bump
     )]
     pub reputation_card: Account<'info, ReputationCard>,
+
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
 }
 
-/// Context for disputing a card
+/// Context for expiring an unresolved dispute
 #[derive(Accounts)]
-pub struct DisputeCard<'info> {
-    /// The recipient who is disputing the card
-    pub recipient: Signer<'info>,
-
-    /// The reputation card to dispute
+pub struct ExpireDispute<'info> {
+    /// The disputed reputation card
     #[account(
         mut,
         seeds = [
@@ -488,6 +2181,277 @@ pub struct DisputeCard<'info> {
         bump
     )]
     pub reputation_card: Account<'info, ReputationCard>,
+
+    /// The profile counting the recipient's cards: its own, or the one it was moved into
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump,
+        constraint = reputation_profile.counts_cards_of(&reputation_card.recipient)
+            @ ReputationCardError::WrongProfile,
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+}
+
+/// Context for recomputing a weighted score
+#[derive(Accounts)]
+pub struct RecomputeScore<'info> {
+    /// Program state account (score half-life)
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The recipient's aggregated reputation profile
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+}
+
+/// Context for decaying a weighted score
+#[derive(Accounts)]
+pub struct RefreshScore<'info> {
+    /// Program state account (score half-life)
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The recipient's aggregated reputation profile
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", reputation_profile.recipient.as_ref()],
+        bump
+    )]
+    pub reputation_profile: Account<'info, ReputationProfile>,
+}
+
+/// Context for moving a migrated wallet's profile
+#[derive(Accounts)]
+pub struct MigrateProfile<'info> {
+    /// Whoever pays for the new wallet's profile, if it has none yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Program state account (score half-life)
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The old wallet's profile
+    #[account(
+        mut,
+        seeds = [b"reputation_profile", old_profile.recipient.as_ref()],
+        bump,
+        constraint = old_profile.moved_to.is_none() @ ReputationCardError::ProfileMoved,
+    )]
+    pub old_profile: Account<'info, ReputationProfile>,
+
+    /// The wallet now holding the identity
+    /// CHECK: Only used as a seed, checked against the identity chain in the handler
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// The new wallet's profile
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ReputationProfile::LEN,
+        seeds = [b"reputation_profile", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_profile: Account<'info, ReputationProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Fixed-point scale for decay factors (1.0 = 10^9)
+const DECAY_ONE: u128 = 1_000_000_000;
+
+/// 2^(-1/2^i) for i = 1..=12, scaled by `DECAY_ONE`
+const DECAY_ROOTS: [u128; 12] = [
+    707_106_781,
+    840_896_415,
+    917_004_043,
+    957_603_281,
+    978_572_062,
+    989_228_013,
+    994_599_423,
+    997_296_056,
+    998_647_113,
+    999_323_328,
+    999_661_606,
+    999_830_789,
+];
+
+/// Scale `value` by 2^(-elapsed / half_life) using integer math only
+/// Whole half-lives are applied as shifts; the remaining fraction is expanded
+/// bit by bit against `DECAY_ROOTS`, so results are identical on every validator.
+/// A `half_life` of 0 leaves the value unchanged.
+fn apply_decay(value: u64, elapsed: i64, half_life: i64) -> Result<u64> {
+    if half_life <= 0 || elapsed <= 0 {
+        return Ok(value);
+    }
+    let elapsed = elapsed as u128;
+    let half_life = half_life as u128;
+
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return Ok(0);
+    }
+    let mut factor = DECAY_ONE;
+    let mut remainder = elapsed % half_life;
+    for root in DECAY_ROOTS {
+        remainder *= 2;
+        if remainder >= half_life {
+            factor = factor * root / DECAY_ONE;
+            remainder -= half_life;
+        }
+    }
+
+    let decayed = (value as u128 >> halvings) * factor / DECAY_ONE;
+    u64::try_from(decayed).map_err(|_| error!(ReputationCardError::Overflow))
+}
+
+/// Most `IdentityLink`s `resolve_identity` follows
+const MAX_IDENTITY_LINKS: usize = 4;
+
+/// Find the identity a wallet's TrustToken identity currently lives at
+/// Reads `wallet`'s trust_token `Identity` PDA from `accounts`. If it is uninitialized
+/// because the identity migrated away, the wallet's `IdentityLink` PDA comes next,
+/// then the `Identity` PDA of the wallet it links to, and so on for up to
+/// `MAX_IDENTITY_LINKS` links. Returns the identity and the number of links followed,
+/// or `None` if the chain ends without an identity.
+fn resolve_identity<'info>(
+    wallet: Pubkey,
+    accounts: &mut std::slice::Iter<'info, AccountInfo<'info>>,
+) -> Result<Option<(Account<'info, Identity>, usize)>> {
+    let mut wallet = wallet;
+    let mut linked_mint = None;
+    for links in 0..=MAX_IDENTITY_LINKS {
+        // Every account is checked against its PDA, so the caller cannot substitute one
+        let identity_info = accounts
+            .next()
+            .ok_or(ReputationCardError::InvalidIdentityChain)?;
+        require_keys_eq!(
+            identity_info.key(),
+            Identity::address(&wallet).0,
+            ReputationCardError::InvalidIdentityChain
+        );
+        if !identity_info.data_is_empty() {
+            let identity = Account::<Identity>::try_from(identity_info)?;
+            // A link leads to the identity it created, not a later one at the same wallet
+            if linked_mint.is_some_and(|mint| mint != identity.mint) {
+                return Ok(None);
+            }
+            return Ok(Some((identity, links)));
+        }
+        if links == MAX_IDENTITY_LINKS {
+            break;
+        }
+
+        let link_info = accounts
+            .next()
+            .ok_or(ReputationCardError::InvalidIdentityChain)?;
+        require_keys_eq!(
+            link_info.key(),
+            IdentityLink::address(&wallet).0,
+            ReputationCardError::InvalidIdentityChain
+        );
+        if link_info.data_is_empty() {
+            return Ok(None);
+        }
+        let link = Account::<IdentityLink>::try_from(link_info)?;
+        if linked_mint.is_some_and(|mint| mint != link.old_mint) {
+            return Ok(None);
+        }
+        linked_mint = Some(link.new_mint);
+        wallet = link.new_owner;
+    }
+    Ok(None)
+}
+
+/// Weight of one card in basis points, from its issuer's standing at `now`
+/// weight = level × age × reputation × purchase, where
+/// - level: Basic 1.0, Enhanced 1.5, Business 2.0
+/// - age: 0.5 rising linearly to 1.0 as the issuer's TrustToken reaches `MATURE_TOKEN_AGE`
+/// - reputation: 0.5 rising linearly to 1.0 as the issuer reaches `MATURE_ACTIVE_CARDS`
+/// - purchase: `VERIFIED_PURCHASE_WEIGHT` for verified purchases, 1.0 otherwise
+/// 
+/// The issuer's standing is read from the wallet now holding its identity, so cards
+/// keep their weight when the issuer migrates. Issuers without an identity or a
+/// currently verified TrustToken weigh 0.
+/// 
+/// Consumes the card's issuer accounts from `accounts` (see `recompute_score`).
+fn card_weight<'info>(
+    card: &ReputationCard,
+    accounts: &mut std::slice::Iter<'info, AccountInfo<'info>>,
+    now: i64,
+) -> Result<u64> {
+    let one = ReputationProfile::WEIGHT_ONE;
+
+    let Some((identity, _)) = resolve_identity(card.issuer, accounts)? else {
+        return Ok(0);
+    };
+    let issuer_trust_token_info = accounts
+        .next()
+        .ok_or(ReputationCardError::InvalidScoreAccounts)?;
+    let issuer_profile_info = accounts
+        .next()
+        .ok_or(ReputationCardError::InvalidScoreAccounts)?;
+    require_keys_eq!(
+        issuer_trust_token_info.key(),
+        identity.trust_token,
+        ReputationCardError::InvalidScoreAccounts
+    );
+    require_keys_eq!(
+        issuer_profile_info.key(),
+        ReputationProfile::address(&identity.owner).0,
+        ReputationCardError::InvalidScoreAccounts
+    );
+    let issuer_trust_token = Account::<TrustToken>::try_from(issuer_trust_token_info)?;
+    if !issuer_trust_token.is_verified_at(now) {
+        return Ok(0);
+    }
+
+    let level_weight = match issuer_trust_token.level {
+        VerificationLevel::Basic => one,
+        VerificationLevel::Enhanced => one * 3 / 2,
+        VerificationLevel::Business => one * 2,
+    };
+
+    let token_age = now
+        .saturating_sub(issuer_trust_token.minted_at)
+        .clamp(0, ReputationProfile::MATURE_TOKEN_AGE) as u64;
+    let age_weight =
+        one / 2 + one / 2 * token_age / ReputationProfile::MATURE_TOKEN_AGE as u64;
+
+    let issuer_active_cards = if issuer_profile_info.data_is_empty() {
+        0
+    } else {
+        Account::<ReputationProfile>::try_from(issuer_profile_info)?.active_count as u64
+    };
+    let reputation_weight = one / 2
+        + one / 2 * issuer_active_cards.min(ReputationProfile::MATURE_ACTIVE_CARDS)
+            / ReputationProfile::MATURE_ACTIVE_CARDS;
+
+    let purchase_weight = if card.verified_purchase {
+        ReputationProfile::VERIFIED_PURCHASE_WEIGHT
+    } else {
+        one
+    };
+
+    Ok(level_weight * age_weight / one * reputation_weight / one * purchase_weight / one)
 }
 
 // ============================================================================
@@ -532,4 +2496,583 @@ pub enum ReputationCardError {
 
     #[msg("Arithmetic underflow occurred")]
     Underflow,
+
+    #[msg("Only the program authority can change program configuration")]
+    UnauthorizedConfig,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Issuer must hold a verified, unexpired TrustToken")]
+    IssuerNotVerified,
+
+    #[msg("Recipient must hold a verified, unexpired TrustToken")]
+    RecipientNotVerified,
+
+    #[msg("Issuers cannot give reputation cards to themselves")]
+    SelfIssuedCard,
+
+    #[msg("A card of this type was already given to this recipient within the cooldown window")]
+    CardCooldownActive,
+
+    #[msg("Issuer has reached the daily card cap")]
+    DailyCardCapReached,
+
+    #[msg("Card cooldown cannot be negative")]
+    InvalidCooldown,
+
+    #[msg("Identity accounts do not follow the wallet's identity links")]
+    InvalidIdentityChain,
+
+    #[msg("This reputation profile was moved to the wallet now holding the identity")]
+    ProfileMoved,
+
+    #[msg("Profile does not count this card's recipient")]
+    WrongProfile,
+
+    #[msg("Profile already counts the maximum number of migrated wallets")]
+    TooManyMergedProfiles,
+
+    #[msg("Cards can only be disputed through dispute_card")]
+    InvalidStatusUpdate,
+
+    #[msg("Accounts passed for score computation do not match the recipient's active cards")]
+    InvalidScoreAccounts,
+
+    #[msg("No score computation in progress, or the recipient's cards changed since it started")]
+    StaleScoreBatch,
+
+    #[msg("Score half-life cannot be negative")]
+    InvalidHalfLife,
+
+    #[msg("An order reference requires the marketplace authority's co-signature")]
+    MissingPurchaseReceipt,
+
+    #[msg("Purchase receipt is not signed by the configured marketplace authority")]
+    InvalidPurchaseReceipt,
+
+    #[msg("An order reference requires its order record account, and only then")]
+    MissingOrderRecord,
+
+    #[msg("Recipient does not match the card")]
+    InvalidRecipient,
+
+    #[msg("Card is not in Disputed status")]
+    CardNotDisputed,
+
+    #[msg("This card has already been disputed")]
+    AlreadyDisputed,
+
+    #[msg("Only the issuer can respond to this dispute")]
+    UnauthorizedResponse,
+
+    #[msg("Dispute response must be 500 characters or less")]
+    DisputeResponseTooLong,
+
+    #[msg("Only the program authority or dispute moderator can resolve disputes")]
+    UnauthorizedResolve,
+
+    #[msg("Invalid dispute outcome")]
+    InvalidDisputeOutcome,
+
+    #[msg("The dispute deadline has passed")]
+    DisputeExpired,
+
+    #[msg("The dispute deadline has not passed yet")]
+    DisputeStillOpen,
+
+    #[msg("Dispute window must be positive")]
+    InvalidDisputeWindow,
+
+    #[msg("Only the issuer can amend this card")]
+    UnauthorizedAmend,
+
+    #[msg("The amendment grace period for this card has passed")]
+    AmendWindowClosed,
+
+    #[msg("Amendment must change the rating or the message")]
+    EmptyAmendment,
+
+    #[msg("This card has reached the maximum number of amendments")]
+    TooManyAmendments,
+
+    #[msg("Disputed cards cannot be amended")]
+    CardDisputed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_decay_halves_per_half_life() {
+        assert_eq!(apply_decay(1_000_000, 0, 100).unwrap(), 1_000_000);
+        assert_eq!(apply_decay(1_000_000, 100, 100).unwrap(), 500_000);
+        assert_eq!(apply_decay(1_000_000, 300, 100).unwrap(), 125_000);
+    }
+
+    #[test]
+    fn apply_decay_handles_fractions_of_a_half_life() {
+        assert_eq!(apply_decay(1_000_000, 25, 100).unwrap(), 840_896);
+        assert_eq!(apply_decay(1_000_000, 50, 100).unwrap(), 707_106);
+        assert_eq!(apply_decay(1_000_000, 75, 100).unwrap(), 594_603);
+        assert_eq!(apply_decay(1_000_000, 150, 100).unwrap(), 353_553);
+
+        let half_life = 180 * 24 * 60 * 60;
+        let elapsed = 97 * 24 * 60 * 60 + 12_345;
+        let expected = 1e12 * 2f64.powf(-(elapsed as f64) / half_life as f64);
+        let decayed = apply_decay(1_000_000_000_000, elapsed, half_life).unwrap() as f64;
+        // The fraction is truncated to 1/4096 of a half-life, 2^(-1/4096) ≈ 1 - 1.7e-4
+        assert!((decayed - expected).abs() / expected < 2e-4);
+    }
+
+    #[test]
+    fn apply_decay_without_half_life_is_identity() {
+        assert_eq!(apply_decay(1_000_000, 1_000_000, 0).unwrap(), 1_000_000);
+        assert_eq!(apply_decay(1_000_000, -5, 100).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn apply_decay_saturates_after_64_halvings() {
+        assert_eq!(apply_decay(u64::MAX, 63 * 100, 100).unwrap(), 1);
+        assert_eq!(apply_decay(u64::MAX, 64 * 100, 100).unwrap(), 0);
+        assert_eq!(apply_decay(u64::MAX, i64::MAX, 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn cooldown_blocks_repeat_cards_for_thirty_days() {
+        let cooldown = ProgramState::DEFAULT_CARD_COOLDOWN;
+        assert_eq!(cooldown, 30 * IssuerStats::SECONDS_PER_DAY);
+        let mut card_pair = CardPairRecord {
+            issuer: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            card_type: CardType::Trustworthy,
+            last_issued_at: 0,
+        };
+
+        let issued_at = 1_700_000_000;
+        card_pair.record_issue(cooldown, issued_at).unwrap();
+        assert_eq!(
+            card_pair.record_issue(cooldown, issued_at + cooldown - 1).unwrap_err(),
+            error!(ReputationCardError::CardCooldownActive)
+        );
+        assert_eq!(card_pair.last_issued_at, issued_at);
+        card_pair.record_issue(cooldown, issued_at + cooldown).unwrap();
+        assert_eq!(card_pair.last_issued_at, issued_at + cooldown);
+
+        // Without a cooldown, repeats are allowed immediately
+        card_pair.record_issue(0, issued_at + cooldown).unwrap();
+    }
+
+    #[test]
+    fn daily_cap_allows_ten_cards_per_utc_day() {
+        let cap = ProgramState::DEFAULT_DAILY_CARD_CAP;
+        assert_eq!(cap, 10);
+        let mut issuer_stats = IssuerStats {
+            issuer: Pubkey::new_unique(),
+            day: 0,
+            cards_today: 0,
+        };
+
+        // The last second of a day
+        let day = 19_700;
+        let late = (day + 1) * IssuerStats::SECONDS_PER_DAY - 1;
+        for _ in 0..cap {
+            issuer_stats.record_issue(cap, late).unwrap();
+        }
+        assert_eq!(
+            issuer_stats.record_issue(cap, late).unwrap_err(),
+            error!(ReputationCardError::DailyCardCapReached)
+        );
+        assert_eq!((issuer_stats.day, issuer_stats.cards_today), (day, cap));
+
+        // The counter resets at midnight UTC
+        issuer_stats.record_issue(cap, late + 1).unwrap();
+        assert_eq!((issuer_stats.day, issuer_stats.cards_today), (day + 1, 1));
+
+        // A cap of 0 is unlimited
+        for _ in 0..2 * cap {
+            issuer_stats.record_issue(0, late + 1).unwrap();
+        }
+    }
+
+    fn profile(recipient: Pubkey) -> ReputationProfile {
+        ReputationProfile {
+            recipient,
+            card_type_counts: [0; CardType::COUNT],
+            rating_sum: 0,
+            active_count: 0,
+            disputed_count: 0,
+            updated_at: 0,
+            weighted_score: 0,
+            total_weight: 0,
+            score_updated_at: 0,
+            revision: 0,
+            pending_weighted_score: 0,
+            pending_total_weight: 0,
+            pending_cards: 0,
+            pending_next_card: 0,
+            pending_started_at: 0,
+            pending_revision: 0,
+            weighted_rating: 0,
+            moved_to: None,
+            merged_from: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn decay_moves_blended_rating_towards_prior() {
+        let one = ReputationProfile::WEIGHT_ONE;
+        let mut profile = profile(Pubkey::default());
+        assert_eq!(profile.blended_rating().unwrap(), 3 * one);
+
+        // Ten 5-star cards of weight 1.0
+        profile.weighted_score = 50 * one;
+        profile.total_weight = 10 * one;
+        let fresh = profile.blended_rating().unwrap();
+        assert_eq!(fresh, 53 * one / 11);
+
+        profile.weighted_score = apply_decay(profile.weighted_score, 100, 100).unwrap();
+        profile.total_weight = apply_decay(profile.total_weight, 100, 100).unwrap();
+        let decayed = profile.blended_rating().unwrap();
+        assert_eq!(decayed, 28 * one / 6);
+        assert!(decayed < fresh && decayed > 3 * one);
+    }
+
+    #[test]
+    fn migrated_profiles_move_their_cards_to_the_new_wallet() {
+        let one = ReputationProfile::WEIGHT_ONE;
+        let old_wallet = Pubkey::new_unique();
+        let new_wallet = Pubkey::new_unique();
+        let mut old = profile(old_wallet);
+        old.card_type_counts[0] = 2;
+        old.rating_sum = 9;
+        old.active_count = 2;
+        old.weighted_score = 9 * one;
+        old.total_weight = 2 * one;
+        let mut new = profile(new_wallet);
+        new.card_type_counts[0] = 1;
+        new.rating_sum = 5;
+        new.active_count = 1;
+        new.weighted_score = 5 * one;
+        new.total_weight = one;
+
+        new.absorb(&mut old, 0, 100).unwrap();
+        assert_eq!(new.card_type_counts[0], 3);
+        assert_eq!((new.rating_sum, new.active_count), (14, 3));
+        assert_eq!((new.weighted_score, new.total_weight), (14 * one, 3 * one));
+        assert_eq!((old.rating_sum, old.active_count), (0, 0));
+        assert_eq!(old.total_weight, 0);
+        assert_eq!(old.moved_to, Some(new_wallet));
+
+        // Each wallet's cards are counted by exactly one profile
+        assert!(!old.counts_cards_of(&old_wallet));
+        assert!(new.counts_cards_of(&old_wallet));
+        assert!(new.counts_cards_of(&new_wallet));
+
+        // Moving back restores the old profile and hands the new wallet's cards over too
+        old.absorb(&mut new, 0, 100).unwrap();
+        assert!(old.counts_cards_of(&old_wallet));
+        assert!(old.counts_cards_of(&new_wallet));
+        assert!(!new.counts_cards_of(&old_wallet));
+        assert!(!new.counts_cards_of(&new_wallet));
+        assert_eq!(old.merged_from, vec![new_wallet]);
+        assert_eq!(old.active_count, 3);
+    }
+
+    #[test]
+    fn moved_profiles_cannot_absorb_unrelated_profiles() {
+        let mut moved = profile(Pubkey::new_unique());
+        moved.moved_to = Some(Pubkey::new_unique());
+        let mut other = profile(Pubkey::new_unique());
+        assert_eq!(
+            moved.absorb(&mut other, 0, 100).unwrap_err(),
+            error!(ReputationCardError::ProfileMoved)
+        );
+    }
+
+    fn active_card(issued_at: i64) -> ReputationCard {
+        ReputationCard {
+            issuer: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            card_type: CardType::Trustworthy,
+            message: String::new(),
+            rating: 4,
+            status: CardStatus::Active,
+            issued_at,
+            revoked_at: None,
+            revocation_reason: None,
+            dispute_reason: None,
+            card_number: 0,
+            order_ref: None,
+            verified_purchase: false,
+            disputed_at: None,
+            dispute_deadline: None,
+            dispute_response: None,
+            dispute_outcome: None,
+            dispute_resolved_at: None,
+            dispute_resolved_by: None,
+            amended_at: None,
+        }
+    }
+
+    #[test]
+    fn disputes_expire_at_their_deadline() {
+        let mut card = active_card(1_000);
+        assert!(!card.is_dispute_expired(i64::MAX));
+
+        card.dispute_deadline = Some(1_000 + ProgramState::DEFAULT_DISPUTE_WINDOW);
+        assert!(!card.is_dispute_expired(999 + ProgramState::DEFAULT_DISPUTE_WINDOW));
+        assert!(card.is_dispute_expired(1_000 + ProgramState::DEFAULT_DISPUTE_WINDOW));
+    }
+
+    #[test]
+    fn amend_allowed_within_grace_period() {
+        let card = active_card(1_000);
+        assert!(card.check_amendable(1_000).is_ok());
+        assert!(card
+            .check_amendable(1_000 + ReputationCard::AMEND_GRACE_PERIOD)
+            .is_ok());
+        assert_eq!(
+            card.check_amendable(1_001 + ReputationCard::AMEND_GRACE_PERIOD)
+                .unwrap_err(),
+            error!(ReputationCardError::AmendWindowClosed)
+        );
+    }
+
+    #[test]
+    fn amend_rejected_after_dispute() {
+        let mut card = active_card(1_000);
+        card.status = CardStatus::Disputed;
+        card.disputed_at = Some(1_100);
+        assert_eq!(
+            card.check_amendable(1_200).unwrap_err(),
+            error!(ReputationCardError::CardNotActive)
+        );
+
+        // Resolved and expired disputes return the card to Active; the outcome must stand
+        for outcome in [DisputeOutcome::Upheld, DisputeOutcome::Amended, DisputeOutcome::Expired] {
+            card.status = CardStatus::Active;
+            card.dispute_outcome = Some(outcome);
+            assert_eq!(
+                card.check_amendable(1_200).unwrap_err(),
+                error!(ReputationCardError::CardDisputed)
+            );
+        }
+    }
+
+    #[test]
+    fn migrated_issuers_keep_their_card_weight() {
+        let now = 1_700_000_000;
+        let old_wallet = Pubkey::new_unique();
+        let new_wallet = Pubkey::new_unique();
+        let old_mint = Pubkey::new_unique();
+        let new_mint = Pubkey::new_unique();
+        let trust_token_key = TrustToken::address(&new_mint).0;
+        let keys = [
+            Identity::address(&old_wallet).0,
+            IdentityLink::address(&old_wallet).0,
+            Identity::address(&new_wallet).0,
+            trust_token_key,
+            ReputationProfile::address(&new_wallet).0,
+        ];
+
+        let mut link_data = Vec::new();
+        IdentityLink {
+            old_owner: old_wallet,
+            new_owner: new_wallet,
+            old_mint,
+            new_mint,
+            approved_by: old_wallet,
+            migrated_at: now - 100,
+        }
+        .try_serialize(&mut link_data)
+        .unwrap();
+        let mut identity_data = Vec::new();
+        Identity {
+            owner: new_wallet,
+            mint: new_mint,
+            trust_token: trust_token_key,
+            created_at: now - 1_000,
+            migrated_from: Some(old_wallet),
+            guardians: Vec::new(),
+            guardian_threshold: 0,
+        }
+        .try_serialize(&mut identity_data)
+        .unwrap();
+        let mut trust_token_data = Vec::new();
+        TrustToken {
+            owner: new_wallet,
+            mint: new_mint,
+            is_verified: true,
+            minted_at: now - 1_000,
+            expires_at: None,
+            level: VerificationLevel::Basic,
+            revoked_at: None,
+            revoked_by: None,
+            compromised_at: None,
+        }
+        .try_serialize(&mut trust_token_data)
+        .unwrap();
+
+        let (mut old_identity_lamports, mut link_lamports, mut identity_lamports) = (0, 1, 1);
+        let (mut trust_token_lamports, mut profile_lamports) = (1, 0);
+        let (mut old_identity_data, mut profile_data) = ([], []);
+        let accounts = [
+            AccountInfo::new(
+                &keys[0],
+                false,
+                false,
+                &mut old_identity_lamports,
+                &mut old_identity_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[1],
+                false,
+                false,
+                &mut link_lamports,
+                &mut link_data,
+                &trust_token::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[2],
+                false,
+                false,
+                &mut identity_lamports,
+                &mut identity_data,
+                &trust_token::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[3],
+                false,
+                false,
+                &mut trust_token_lamports,
+                &mut trust_token_data,
+                &trust_token::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[4],
+                false,
+                false,
+                &mut profile_lamports,
+                &mut profile_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+        ];
+
+        // A card the issuer gave from the old wallet weighs as if given from the new one
+        let mut card = active_card(now);
+        card.issuer = old_wallet;
+        let mut remaining = accounts.iter();
+        assert!(card_weight(&card, &mut remaining, now).unwrap() > 0);
+        assert!(remaining.next().is_none());
+
+        // The chain must be passed in full, in order
+        let out_of_order: Vec<_> = [&accounts[0], &accounts[2], &accounts[3], &accounts[4]]
+            .into_iter()
+            .cloned()
+            .collect();
+        assert_eq!(
+            card_weight(&card, &mut out_of_order.iter(), now).unwrap_err(),
+            error!(ReputationCardError::InvalidIdentityChain)
+        );
+    }
+
+    #[test]
+    fn verified_purchases_weigh_more() {
+        let now = 1_700_000_000;
+        let issuer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let identity_key = Identity::address(&issuer).0;
+        let trust_token_key = TrustToken::address(&mint).0;
+        let profile_key = ReputationProfile::address(&issuer).0;
+
+        let mut identity_data = Vec::new();
+        Identity {
+            owner: issuer,
+            mint,
+            trust_token: trust_token_key,
+            created_at: now - 1_000,
+            migrated_from: None,
+            guardians: Vec::new(),
+            guardian_threshold: 0,
+        }
+        .try_serialize(&mut identity_data)
+        .unwrap();
+        let mut trust_token_data = Vec::new();
+        TrustToken {
+            owner: issuer,
+            mint,
+            is_verified: true,
+            minted_at: now - 1_000,
+            expires_at: None,
+            level: VerificationLevel::Basic,
+            revoked_at: None,
+            revoked_by: None,
+            compromised_at: None,
+        }
+        .try_serialize(&mut trust_token_data)
+        .unwrap();
+
+        let (mut identity_lamports, mut trust_token_lamports, mut profile_lamports) = (1, 1, 0);
+        let mut profile_data = [];
+        let accounts = [
+            AccountInfo::new(
+                &identity_key,
+                false,
+                false,
+                &mut identity_lamports,
+                &mut identity_data,
+                &trust_token::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &trust_token_key,
+                false,
+                false,
+                &mut trust_token_lamports,
+                &mut trust_token_data,
+                &trust_token::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &profile_key,
+                false,
+                false,
+                &mut profile_lamports,
+                &mut profile_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+        ];
+
+        let mut card = active_card(now);
+        card.issuer = issuer;
+        let weight = card_weight(&card, &mut accounts.iter(), now).unwrap();
+        assert!(weight > 0);
+
+        card.verified_purchase = true;
+        let verified_weight = card_weight(&card, &mut accounts.iter(), now).unwrap();
+        assert_eq!(
+            verified_weight,
+            weight * ReputationProfile::VERIFIED_PURCHASE_WEIGHT / ReputationProfile::WEIGHT_ONE
+        );
+    }
 }
@@ -1,11 +1,49 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, transfer, CreateAccount, Transfer},
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{
+        create as create_associated_token_account, AssociatedToken,
+        Create as CreateAssociatedTokenAccount,
+    },
     metadata::{
-        create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3,
-        CreateMetadataAccountsV3, Metadata, mpl_token_metadata::types::{Creator, DataV2},
+        create_master_edition_v3, create_metadata_accounts_v3, thaw_delegated_account,
+        update_metadata_accounts_v2, verify_sized_collection_item, CreateMasterEditionV3,
+        CreateMetadataAccountsV3, Metadata, MetadataAccount, ThawDelegatedAccount,
+        UpdateMetadataAccountsV2, VerifySizedCollectionItem,
+        mpl_token_metadata::types::{Collection, CollectionDetails, Creator, DataV2},
+    },
+    token::{
+        approve, burn, freeze_account, mint_to, Approve, Burn, FreezeAccount, Mint, MintTo, Token,
+        TokenAccount,
+    },
+    token_2022::{
+        self, initialize_mint2,
+        spl_token_2022::{
+            extension::{
+                non_transferable::NonTransferable, BaseStateWithExtensions, ExtensionType,
+                StateWithExtensions,
+            },
+            instruction::AuthorityType,
+            state::Mint as Token2022Mint,
+        },
+        InitializeMint2, Token2022,
+    },
+    token_2022_extensions::{
+        group_member_pointer_initialize, group_pointer_initialize, metadata_pointer_initialize,
+        non_transferable_mint_initialize, permanent_delegate_initialize,
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata}, token_group_initialize,
+        token_member_initialize, token_metadata_initialize, token_metadata_update_field,
+        GroupMemberPointerInitialize, GroupPointerInitialize, MetadataPointerInitialize,
+        NonTransferableMintInitialize, PermanentDelegateInitialize, TokenGroupInitialize,
+        TokenMemberInitialize, TokenMetadataInitialize, TokenMetadataUpdateField,
+    },
+    token_interface::{
+        self, get_mint_extension_data, Mint as InterfaceMint,
+        TokenAccount as InterfaceTokenAccount, TokenInterface,
     },
-    token::{mint_to, freeze_account, burn, Mint, MintTo, Token, TokenAccount, FreezeAccount, Burn},
 };
 
 declare_id!("3gUohiKvtQGZ2gXdimtvtVxy3JEFC9mTs3fLuo4ox5Ju");
@@ -27,6 +65,16 @@ pub mod trust_token {
         let program_state = &mut ctx.accounts.program_state;
         program_state.authority = ctx.accounts.authority.key();
         program_state.total_minted = 0;
+        program_state.mint_policy = MintPolicy::Open;
+        program_state.pending_authority = None;
+        program_state.verification_period = None;
+        program_state.total_burned = 0;
+        program_state.report_bounty = 0;
+
+        emit!(ProgramInitialized {
+            authority: program_state.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         msg!("TrustToken program initialized by authority: {}", program_state.authority);
         Ok(())
@@ -43,10 +91,19 @@ pub mod trust_token {
     /// * `uri` - The metadata URI pointing to off-chain data
     /// 
     /// # Security
-    /// - Users can self-mint their own verification tokens
+    /// - Who may mint is governed by `ProgramState::mint_policy`:
+    ///   `Open` allows self-minting, `AuthorityOnly` requires the authority to co-sign,
+    ///   `AttestorSigned` accepts a co-signature from the authority or an active attestor
+    ///   with the mint-approve scope
+    /// - A wallet that burned a revoked TrustToken (see `Tombstone`) needs that
+    ///   co-signature even when minting is `Open`
+    /// - Each wallet can mint only once: the `identity` PDA is keyed by the minter
+    /// - Verification expires after `ProgramState::verification_period`, if one is set
     /// - Each mint creates a unique NFT with supply of 1
     /// - Master edition ensures true NFT (non-fungible) properties
     /// - Token account is FROZEN immediately after minting (Soulbound)
+    /// - Mint and freeze authority belong to the program's `mint_authority` PDA,
+    ///   which is also approved as delegate so only the program can thaw or burn
     pub fn mint(
         ctx: Context<MintTrustToken>,
         name: String,
@@ -58,32 +115,200 @@ pub mod trust_token {
         require!(symbol.len() <= 10, TrustTokenError::SymbolTooLong);
         require!(uri.len() <= 200, TrustTokenError::UriTooLong);
 
-        // Mint 1 token to the user's associated token account
-        let cpi_context = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
+        // Enforce the minting policy before any accounts are touched
+        check_mint_policy(
+            &ctx.accounts.program_state,
+            ctx.accounts.approver.as_ref(),
+            ctx.accounts.attestor_record.as_ref(),
+            !ctx.accounts.tombstone.data_is_empty(),
+        )?;
+
+        // The mint authority PDA signs every CPI that needs mint/freeze authority
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+
+        // Mint, freeze and attach Metaplex metadata, then verify into the collection
+        mint_metaplex_token(
+            MetaplexMint {
+                owner: ctx.accounts.minter.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.token_account.to_account_info(),
-                authority: ctx.accounts.minter.to_account_info(),
+                token_account: ctx.accounts.token_account.to_account_info(),
+                metadata: ctx.accounts.metadata.to_account_info(),
+                master_edition: ctx.accounts.master_edition.to_account_info(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
             },
+            name,
+            symbol,
+            uri,
+            signer_seeds,
+        )?;
+
+        // Initialize the TrustToken and identity records, update program state
+        record_mint(
+            &mut ctx.accounts.program_state,
+            &mut ctx.accounts.trust_token,
+            &mut ctx.accounts.identity,
+            ctx.accounts.minter.key(),
+            ctx.accounts.mint.key(),
+            ctx.accounts.approver.as_ref().map(|approver| approver.key()),
+        )?;
+
+        msg!(
+            "TrustToken NFT minted to: {} | Total minted: {}",
+            ctx.accounts.minter.key(),
+            ctx.accounts.program_state.total_minted
         );
-        mint_to(cpi_context, 1)?;
 
-        // SOULBOUND: Freeze the token account immediately after minting
-        // This prevents any transfers, making the NFT permanently bound to this wallet
-        let freeze_cpi_context = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            FreezeAccount {
-                account: ctx.accounts.token_account.to_account_info(),
+        Ok(())
+    }
+
+    /// Mint a Soulbound TrustToken as a Token-2022 NonTransferable mint
+    /// Alternative to `mint` that makes transfers impossible at the token-program level
+    /// instead of relying on a frozen account. Metadata lives on the mint itself through
+    /// the MetadataPointer and TokenMetadata extensions, with the program PDA as update authority.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for minting
+    /// * `name` - The name of the NFT (e.g., "Trust Token #1")
+    /// * `symbol` - The symbol of the NFT (e.g., "TRUST")
+    /// * `uri` - The metadata URI pointing to off-chain data
+    /// 
+    /// # Security
+    /// - Same minting policy and one-per-wallet rule as `mint`
+    /// - Mint authority is removed after minting, fixing supply at 1
+    /// - No `burn_transferred_token` policing is needed for these tokens
+    /// - Joins the Token-2022 TrustToken group (see `initialize_collection_v2`), the
+    ///   counterpart of the Metaplex collection that `mint` verifies into
+    pub fn mint_v2(
+        ctx: Context<MintTrustTokenV2>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        // Validate input lengths to prevent excessive storage costs
+        require!(name.len() <= 32, TrustTokenError::NameTooLong);
+        require!(symbol.len() <= 10, TrustTokenError::SymbolTooLong);
+        require!(uri.len() <= 200, TrustTokenError::UriTooLong);
+
+        // Enforce the minting policy before any accounts are touched
+        check_mint_policy(
+            &ctx.accounts.program_state,
+            ctx.accounts.approver.as_ref(),
+            ctx.accounts.attestor_record.as_ref(),
+            !ctx.accounts.tombstone.data_is_empty(),
+        )?;
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+        let mint_key = ctx.accounts.mint.key();
+
+        // Create the NonTransferable mint with its metadata and group membership,
+        // and mint the single token to the minter
+        mint_non_transferable_token(
+            NonTransferableMint {
+                owner: ctx.accounts.minter.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
-                authority: ctx.accounts.minter.to_account_info(),
+                token_account: ctx.accounts.token_account.to_account_info(),
+                collection_group: ctx.accounts.collection_group.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            name,
+            symbol,
+            uri,
+            VerificationLevel::Basic,
+            signer_seeds,
+        )?;
+
+        // Initialize the TrustToken and identity records, update program state
+        record_mint(
+            &mut ctx.accounts.program_state,
+            &mut ctx.accounts.trust_token,
+            &mut ctx.accounts.identity,
+            ctx.accounts.minter.key(),
+            mint_key,
+            ctx.accounts.approver.as_ref().map(|approver| approver.key()),
+        )?;
+
+        msg!(
+            "Token-2022 TrustToken minted to: {} | Total minted: {}",
+            ctx.accounts.minter.key(),
+            ctx.accounts.program_state.total_minted
+        );
+
+        Ok(())
+    }
+
+    /// Initialize the TrustToken collection NFT
+    /// Every TrustToken minted through `mint` is attached to and verified into this
+    /// sized collection, so consumers can check authenticity by collection membership.
+    /// The program PDA holds the collection NFT and is its update authority.
+    /// Must be called once before the first `mint`.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the collection
+    /// * `name` - The name of the collection NFT (e.g., "Trust Tokens")
+    /// * `symbol` - The symbol of the collection NFT (e.g., "TRUST")
+    /// * `uri` - The metadata URI pointing to off-chain data
+    /// 
+    /// # Security
+    /// - Only the program authority can create the collection
+    /// - Only callable once due to the `init` constraint on the collection mint PDA
+    pub fn initialize_collection(
+        ctx: Context<InitializeCollection>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        // Security check: Only authority can create the collection
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+
+        // Validate input lengths to prevent excessive storage costs
+        require!(name.len() <= 32, TrustTokenError::NameTooLong);
+        require!(symbol.len() <= 10, TrustTokenError::SymbolTooLong);
+        require!(uri.len() <= 200, TrustTokenError::UriTooLong);
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+
+        // Mint the single collection NFT to the PDA's token account
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                to: ctx.accounts.collection_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
             },
+            signer_seeds,
         );
-        freeze_account(freeze_cpi_context)?;
+        mint_to(cpi_context, 1)?;
 
-        // Create metadata account for the NFT
         let creator = vec![Creator {
-            address: ctx.accounts.minter.key(),
+            address: ctx.accounts.mint_authority.key(),
             verified: true,
             share: 100,
         }];
@@ -92,416 +317,4683 @@ pub mod trust_token {
             name,
             symbol,
             uri,
-            seller_fee_basis_points: 0, // No royalties for identity tokens
+            seller_fee_basis_points: 0,
             creators: Some(creator),
             collection: None,
             uses: None,
         };
 
-        let cpi_context = CpiContext::new(
+        // Sized collection, so Metaplex tracks the member count as tokens are verified in
+        let cpi_context = CpiContext::new_with_signer(
             ctx.accounts.token_metadata_program.to_account_info(),
             CreateMetadataAccountsV3 {
-                metadata: ctx.accounts.metadata.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                mint_authority: ctx.accounts.minter.to_account_info(),
-                update_authority: ctx.accounts.minter.to_account_info(),
-                payer: ctx.accounts.minter.to_account_info(),
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
                 system_program: ctx.accounts.system_program.to_account_info(),
                 rent: ctx.accounts.rent.to_account_info(),
             },
+            signer_seeds,
         );
-        create_metadata_accounts_v3(cpi_context, metadata_data, true, true, None)?;
+        create_metadata_accounts_v3(
+            cpi_context,
+            metadata_data,
+            true,
+            true,
+            Some(CollectionDetails::V1 { size: 0 }),
+        )?;
 
-        // Create master edition to make this a true NFT (supply = 1, no more can be minted)
-        let cpi_context = CpiContext::new(
+        let cpi_context = CpiContext::new_with_signer(
             ctx.accounts.token_metadata_program.to_account_info(),
             CreateMasterEditionV3 {
-                edition: ctx.accounts.master_edition.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                update_authority: ctx.accounts.minter.to_account_info(),
-                mint_authority: ctx.accounts.minter.to_account_info(),
-                payer: ctx.accounts.minter.to_account_info(),
-                metadata: ctx.accounts.metadata.to_account_info(),
+                edition: ctx.accounts.collection_master_edition.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
                 token_program: ctx.accounts.token_program.to_account_info(),
                 system_program: ctx.accounts.system_program.to_account_info(),
                 rent: ctx.accounts.rent.to_account_info(),
             },
+            signer_seeds,
         );
-        create_master_edition_v3(cpi_context, Some(0))?; // max_supply = 0 means only 1 can exist
-
-        // Initialize the TrustToken account data
-        let trust_token = &mut ctx.accounts.trust_token;
-        trust_token.owner = ctx.accounts.minter.key();
-        trust_token.mint = ctx.accounts.mint.key();
-        trust_token.is_verified = true; // Set to true upon self-minting
-        trust_token.minted_at = Clock::get()?.unix_timestamp;
+        create_master_edition_v3(cpi_context, Some(0))?;
 
-        // Update program state
-        let program_state = &mut ctx.accounts.program_state;
-        program_state.total_minted = program_state
-            .total_minted
-            .checked_add(1)
-            .ok_or(TrustTokenError::Overflow)?;
+        emit!(CollectionInitialized {
+            collection_mint: ctx.accounts.collection_mint.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         msg!(
-            "TrustToken NFT minted to: {} | Total minted: {}",
-            trust_token.owner,
-            program_state.total_minted
+            "TrustToken collection initialized: {}",
+            ctx.accounts.collection_mint.key()
         );
-
         Ok(())
     }
 
-    /// Revoke verification status of a TrustToken
-    /// This allows the authority to revoke a user's verified status without burning the NFT.
+    /// Initialize the Token-2022 TrustToken group
+    /// The `mint_v2` counterpart of the Metaplex collection: a Token-2022 mint with the
+    /// TokenGroup extension that every `mint_v2` token joins as a TokenGroupMember, so
+    /// consumers can check authenticity by group membership. The program PDA is the
+    /// group's update authority. Must be called once before the first `mint_v2`.
     /// 
     /// # Arguments
-    /// * `ctx` - The context containing all accounts needed for revocation
+    /// * `ctx` - The context containing all accounts needed for the group
+    /// * `name` - The name of the group (e.g., "Trust Tokens")
+    /// * `symbol` - The symbol of the group (e.g., "TRUST")
+    /// * `uri` - The metadata URI pointing to off-chain data
     /// 
     /// # Security
-    /// - Only the program authority can revoke verification
-    pub fn revoke_verification(ctx: Context<RevokeVerification>) -> Result<()> {
-        // Security check: Only authority can revoke
+    /// - Only the program authority can create the group
+    /// - Only callable once, as the group mint PDA cannot be created twice
+    pub fn initialize_collection_v2(
+        ctx: Context<InitializeCollectionV2>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        // Security check: Only authority can create the group
         require!(
             ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
-            TrustTokenError::UnauthorizedRevoke
+            TrustTokenError::UnauthorizedConfig
         );
 
-        let trust_token = &mut ctx.accounts.trust_token;
-        trust_token.is_verified = false;
+        // Validate input lengths to prevent excessive storage costs
+        require!(name.len() <= 32, TrustTokenError::NameTooLong);
+        require!(symbol.len() <= 10, TrustTokenError::SymbolTooLong);
+        require!(uri.len() <= 200, TrustTokenError::UriTooLong);
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+        let group_seeds: &[&[&[u8]]] = &[&[
+            b"collection_group",
+            program_state_key.as_ref(),
+            &[ctx.bumps.collection_group],
+        ]];
+        let mint_authority_key = ctx.accounts.mint_authority.key();
+        let group_key = ctx.accounts.collection_group.key();
+
+        // Size the mint for its pointers; fund it for the group and metadata TLVs too
+        let mint_len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+            ExtensionType::GroupPointer,
+            ExtensionType::MetadataPointer,
+        ])?;
+        let funded_len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+            ExtensionType::GroupPointer,
+            ExtensionType::MetadataPointer,
+            ExtensionType::TokenGroup,
+        ])?;
+        let token_metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(mint_authority_key))?,
+            mint: group_key,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: vec![],
+        };
+        let lamports = Rent::get()?.minimum_balance(funded_len + token_metadata.tlv_size_of()?);
+
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.collection_group.to_account_info(),
+                },
+                group_seeds,
+            ),
+            lamports,
+            mint_len as u64,
+            &ctx.accounts.token_program.key(),
+        )?;
+
+        group_pointer_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                GroupPointerInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.collection_group.to_account_info(),
+                },
+            ),
+            Some(mint_authority_key),
+            Some(group_key),
+        )?;
+        metadata_pointer_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.collection_group.to_account_info(),
+                },
+            ),
+            Some(mint_authority_key),
+            Some(group_key),
+        )?;
+        initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.collection_group.to_account_info(),
+                },
+            ),
+            0,
+            &mint_authority_key,
+            None,
+        )?;
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: ctx.accounts.collection_group.to_account_info(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                    mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                    mint: ctx.accounts.collection_group.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            name,
+            symbol,
+            uri,
+        )?;
+        token_group_initialize(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenGroupInitialize {
+                    program_id: ctx.accounts.token_program.to_account_info(),
+                    group: ctx.accounts.collection_group.to_account_info(),
+                    mint: ctx.accounts.collection_group.to_account_info(),
+                    mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Some(mint_authority_key),
+            u64::MAX,
+        )?;
 
-        msg!("Verification revoked for TrustToken: {}", trust_token.mint);
+        emit!(CollectionInitialized {
+            collection_mint: group_key,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("TrustToken group initialized: {}", group_key);
         Ok(())
     }
 
-    /// Restore verification status of a TrustToken
-    /// This allows the authority to restore a user's verified status.
+    /// Set the minting policy
+    /// Controls whether TrustTokens can be self-minted or need an approving co-signer.
     /// 
     /// # Arguments
-    /// * `ctx` - The context containing all accounts needed for restoration
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `mint_policy` - The new minting policy
     /// 
     /// # Security
-    /// - Only the program authority can restore verification
-    pub fn restore_verification(ctx: Context<RestoreVerification>) -> Result<()> {
-        // Security check: Only authority can restore
+    /// - Only the program authority can change the minting policy
+    pub fn set_mint_policy(ctx: Context<SetMintPolicy>, mint_policy: MintPolicy) -> Result<()> {
+        // Security check: Only authority can change configuration
         require!(
             ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
-            TrustTokenError::UnauthorizedRestore
+            TrustTokenError::UnauthorizedConfig
         );
 
-        let trust_token = &mut ctx.accounts.trust_token;
-        trust_token.is_verified = true;
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.mint_policy = mint_policy;
 
-        msg!("Verification restored for TrustToken: {}", trust_token.mint);
+        emit!(MintPolicyUpdated {
+            mint_policy,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Mint policy set to {:?}", mint_policy);
         Ok(())
     }
 
-    /// Verify that a TrustToken is still bound to its original owner
-    /// This checks if the token is in the correct wallet and hasn't been transferred.
-    /// If the token has been moved, it can be burned by the authority.
+    /// Propose a new program authority
+    /// First step of a two-step handover; the proposed key must call `accept_authority`.
+    /// Proposing again replaces any pending proposal.
     /// 
     /// # Arguments
-    /// * `ctx` - The context containing all accounts needed for verification
+    /// * `ctx` - The context containing all accounts needed for the proposal
+    /// * `new_authority` - The key that will become the authority once it accepts
     /// 
-    /// # Returns
-    /// - Ok(()) if the token is still bound to the original owner
-    /// - Error if the token has been transferred
-    pub fn verify_soulbound(ctx: Context<VerifySoulbound>) -> Result<()> {
-        let trust_token = &ctx.accounts.trust_token;
-        let token_account = &ctx.accounts.token_account;
-
-        // Check if the token account owner matches the original TrustToken owner
+    /// # Security
+    /// - Only the current program authority can propose a successor
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        // Security check: Only authority can propose a successor
         require!(
-            token_account.owner == trust_token.owner,
-            TrustTokenError::TokenTransferred
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
         );
 
-        // Check if the token account is frozen (should always be frozen for soulbound)
-        require!(
-            token_account.is_frozen(),
-            TrustTokenError::TokenNotFrozen
-        );
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.pending_authority = Some(new_authority);
 
-        // Check if the token account has exactly 1 token
-        require!(
-            token_account.amount == 1,
-            TrustTokenError::InvalidTokenAmount
-        );
+        emit!(AuthorityProposed {
+            authority: program_state.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        msg!("TrustToken {} is properly soulbound to {}", trust_token.mint, trust_token.owner);
+        msg!("Authority transfer proposed: {} → {}", program_state.authority, new_authority);
         Ok(())
     }
 
-    /// Burn a TrustToken that has been transferred (violating soulbound property)
-    /// This can only be called by the program authority as a security measure.
+    /// Accept a pending authority transfer
+    /// Second step of the handover; the signer becomes the program authority.
     /// 
     /// # Arguments
-    /// * `ctx` - The context containing all accounts needed for burning
+    /// * `ctx` - The context containing all accounts needed for acceptance
     /// 
     /// # Security
-    /// - Only the program authority can burn tokens
-    /// - This is used to enforce the soulbound property
-    pub fn burn_transferred_token(ctx: Context<BurnTransferredToken>) -> Result<()> {
-        // Security check: Only authority can burn
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
-            TrustTokenError::UnauthorizedBurn
-        );
-
-        let trust_token = &ctx.accounts.trust_token;
-        let token_account = &ctx.accounts.token_account;
+    /// - Only the pending authority can accept
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let program_state = &mut ctx.accounts.program_state;
 
-        // Verify that the token has indeed been transferred (not in original owner's wallet)
+        // Security check: Only the proposed key can accept
         require!(
-            token_account.owner != trust_token.owner,
-            TrustTokenError::TokenNotTransferred
+            program_state.pending_authority == Some(ctx.accounts.new_authority.key()),
+            TrustTokenError::NotPendingAuthority
         );
 
-        // Burn the token
-        let cpi_context = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.mint.to_account_info(),
-                from: ctx.accounts.token_account.to_account_info(),
-                authority: ctx.accounts.authority.to_account_info(),
-            },
-        );
-        burn(cpi_context, 1)?;
+        let old_authority = program_state.authority;
+        program_state.authority = ctx.accounts.new_authority.key();
+        program_state.pending_authority = None;
 
-        msg!("Burned transferred TrustToken: {} (was transferred from {})", 
-            trust_token.mint, trust_token.owner);
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: program_state.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transferred: {} → {}", old_authority, program_state.authority);
         Ok(())
     }
-}
 
-// ============================================================================
-// Account Structures
-// ============================================================================
+    /// Migrate the program state account to the current layout
+    /// A `ProgramState` created by an earlier deployment is shorter than `ProgramState::LEN`
+    /// and no longer deserializes. This grows it in place; the appended fields start zeroed,
+    /// i.e. open minting, no pending authority, no verification expiry and no report bounty,
+    /// which is how the program behaved before the upgrade.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the program state and the paying authority
+    /// 
+    /// # Security
+    /// - Only the authority recorded in the old account can migrate it
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        let program_state = ctx.accounts.program_state.to_account_info();
+        {
+            let data = program_state.try_borrow_data()?;
+            require!(
+                data.starts_with(ProgramState::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            // Security check: Only authority can migrate (the first field in every layout)
+            require!(
+                data.get(8..40) == Some(ctx.accounts.authority.key().as_ref()),
+                TrustTokenError::UnauthorizedConfig
+            );
+        }
 
-/// Program state account that stores global configuration
-#[account]
-pub struct ProgramState {
-    /// The authority that can mint and manage TrustTokens
-    pub authority: Pubkey,
-    /// Total number of TrustTokens minted
-    pub total_minted: u64,
-}
+        let old_len = program_state.data_len();
+        require!(old_len < ProgramState::LEN, TrustTokenError::AlreadyMigrated);
+        grow_account(
+            &program_state,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ProgramState::LEN,
+        )?;
 
-impl ProgramState {
-    /// Calculate the space needed for this account
-    pub const LEN: usize = 8 + // discriminator
-        32 + // authority
-        8; // total_minted
-}
+        emit!(AccountMigrated {
+            account: program_state.key(),
+            old_len: old_len as u32,
+            new_len: ProgramState::LEN as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-/// TrustToken account that stores NFT-specific data
-/// This account is created for each minted TrustToken and stores verification status
-#[account]
-pub struct TrustToken {
-    /// The owner of this TrustToken
-    pub owner: Pubkey,
-    /// The mint address of this TrustToken NFT
-    pub mint: Pubkey,
-    /// Whether this token represents a verified user
-    pub is_verified: bool,
-    /// Timestamp when the token was minted
-    pub minted_at: i64,
-}
+        msg!("Program state migrated: {} → {} bytes", old_len, ProgramState::LEN);
+        Ok(())
+    }
 
-impl TrustToken {
-    /// Calculate the space needed for this account
-    pub const LEN: usize = 8 + // discriminator
-        32 + // owner
-        32 + // mint
-        1 + // is_verified
-        8; // minted_at
-}
+    /// Migrate a TrustToken account to the current layout
+    /// Grows a TrustToken created by an earlier deployment to `TrustToken::LEN` (the appended
+    /// fields start zeroed: no expiry, Basic level, never revoked or reported) and creates the
+    /// owner's identity record if the wallet does not have one yet.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the TrustToken, its owner's identity and the payer
+    /// 
+    /// # Security
+    /// - Permissionless: the payer only covers rent, every stored value comes from the old account
+    pub fn migrate_trust_token_account(ctx: Context<MigrateTrustTokenAccount>) -> Result<()> {
+        let trust_token_info = ctx.accounts.trust_token.to_account_info();
+        {
+            let data = trust_token_info.try_borrow_data()?;
+            require!(
+                data.starts_with(TrustToken::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            // The identity is seeded by `owner`, so it must be the owner stored in the token
+            require!(
+                data.get(8..40) == Some(ctx.accounts.owner.key().as_ref()),
+                TrustTokenError::NotTokenOwner
+            );
+        }
 
-// ============================================================================
-// Context Structures
-// ============================================================================
+        let old_len = trust_token_info.data_len();
+        require!(old_len < TrustToken::LEN, TrustTokenError::AlreadyMigrated);
+        grow_account(
+            &trust_token_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            TrustToken::LEN,
+        )?;
+        let trust_token = TrustToken::try_deserialize(&mut &trust_token_info.try_borrow_data()?[..])?;
 
-/// Context for initializing the TrustToken program
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    /// The authority that will control the program
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        // Link the wallet to this token unless it already has an identity
+        let identity = &mut ctx.accounts.identity;
+        if identity.owner == Pubkey::default() {
+            identity.owner = trust_token.owner;
+            identity.mint = trust_token.mint;
+            identity.trust_token = trust_token_info.key();
+            identity.created_at = trust_token.minted_at;
+            identity.migrated_from = None;
+            identity.guardians = Vec::new();
+            identity.guardian_threshold = 0;
+        }
 
-    /// Program state account - stores global configuration
-    /// Seeds ensure only one program state can exist
-    #[account(
-        init,
-        payer = authority,
-        space = ProgramState::LEN,
-        seeds = [b"program_state"],
-        bump
-    )]
-    pub program_state: Account<'info, ProgramState>,
+        emit!(AccountMigrated {
+            account: trust_token_info.key(),
+            old_len: old_len as u32,
+            new_len: TrustToken::LEN as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    pub system_program: Program<'info, System>,
-}
+        msg!("TrustToken {} migrated for owner {}", trust_token.mint, trust_token.owner);
+        Ok(())
+    }
+
+    /// Set how long a verification stays valid
+    /// Applies to tokens minted or renewed after the change; existing expiries are untouched.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `verification_period` - Validity in seconds, or `None` for verifications that never expire
+    /// 
+    /// # Security
+    /// - Only the program authority can change the verification period
+    pub fn set_verification_period(
+        ctx: Context<SetVerificationPeriod>,
+        verification_period: Option<i64>,
+    ) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+        if let Some(period) = verification_period {
+            require!(period > 0, TrustTokenError::InvalidVerificationPeriod);
+        }
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.verification_period = verification_period;
+
+        emit!(VerificationPeriodUpdated {
+            verification_period,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Verification period set to {:?} seconds", verification_period);
+        Ok(())
+    }
+
+    /// Set the bounty paid for soulbound violation reports
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `report_bounty` - Lamports paid from the fee vault per valid report (0 disables the bounty)
+    /// 
+    /// # Security
+    /// - Only the program authority can change the bounty
+    pub fn set_report_bounty(ctx: Context<SetReportBounty>, report_bounty: u64) -> Result<()> {
+        // Security check: Only authority can change configuration
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.report_bounty = report_bounty;
+
+        emit!(ReportBountyUpdated {
+            report_bounty,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Report bounty set to {} lamports", report_bounty);
+        Ok(())
+    }
+
+    /// Deposit lamports into the program's fee vault
+    /// The vault pays out report bounties. Anyone can top it up.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the deposit
+    /// * `amount` - Lamports to deposit
+    pub fn fund_fee_vault(ctx: Context<FundFeeVault>, amount: u64) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(FeeVaultFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            balance: ctx.accounts.fee_vault.lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Fee vault funded with {} lamports", amount);
+        Ok(())
+    }
+
+    /// Register an attestor
+    /// Attestors act on behalf of the authority for the actions their scopes allow.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for registration
+    /// * `scopes` - Bitmask of `Attestor::SCOPE_*` actions the attestor may perform
+    /// * `expires_at` - Optional timestamp after which the attestor can no longer act
+    /// 
+    /// # Security
+    /// - Only the program authority can register attestors
+    /// - One attestor record per key, enforced by the `init` constraint
+    pub fn add_attestor(
+        ctx: Context<AddAttestor>,
+        scopes: u8,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        // Security check: Only authority can register attestors
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+        require!(
+            scopes & !Attestor::SCOPE_ALL == 0,
+            TrustTokenError::InvalidAttestorScopes
+        );
+
+        let attestor_record = &mut ctx.accounts.attestor_record;
+        attestor_record.attestor = ctx.accounts.attestor.key();
+        attestor_record.added_by = ctx.accounts.authority.key();
+        attestor_record.added_at = Clock::get()?.unix_timestamp;
+        attestor_record.scopes = scopes;
+        attestor_record.expires_at = expires_at;
+
+        emit!(AttestorRegistered {
+            attestor: attestor_record.attestor,
+            scopes,
+            expires_at,
+            added_by: attestor_record.added_by,
+            timestamp: attestor_record.added_at,
+        });
+
+        msg!(
+            "Attestor registered: {} (scopes: {:#06b}, expires: {:?})",
+            attestor_record.attestor,
+            scopes,
+            expires_at
+        );
+        Ok(())
+    }
+
+    /// Update an attestor's scopes and expiry
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `scopes` - Bitmask of `Attestor::SCOPE_*` actions the attestor may perform
+    /// * `expires_at` - Optional timestamp after which the attestor can no longer act
+    /// 
+    /// # Security
+    /// - Only the program authority can update attestors
+    pub fn update_attestor(
+        ctx: Context<UpdateAttestor>,
+        scopes: u8,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        // Security check: Only authority can update attestors
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+        require!(
+            scopes & !Attestor::SCOPE_ALL == 0,
+            TrustTokenError::InvalidAttestorScopes
+        );
+
+        let attestor_record = &mut ctx.accounts.attestor_record;
+        attestor_record.scopes = scopes;
+        attestor_record.expires_at = expires_at;
+
+        emit!(AttestorUpdated {
+            attestor: attestor_record.attestor,
+            scopes,
+            expires_at,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Attestor updated: {} (scopes: {:#06b}, expires: {:?})",
+            attestor_record.attestor,
+            scopes,
+            expires_at
+        );
+        Ok(())
+    }
+
+    /// Remove an attestor
+    /// Closes the attestor record and returns its rent to the authority.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for removal
+    /// 
+    /// # Security
+    /// - Only the program authority can remove attestors
+    pub fn remove_attestor(ctx: Context<RemoveAttestor>) -> Result<()> {
+        // Security check: Only authority can remove attestors
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+
+        emit!(AttestorRemoved {
+            attestor: ctx.accounts.attestor_record.attestor,
+            removed_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Attestor removed: {}", ctx.accounts.attestor_record.attestor);
+        Ok(())
+    }
+
+    /// Revoke verification status of a TrustToken
+    /// This allows the authority to revoke a user's verified status without burning the NFT.
+    /// The revocation is stamped on the TrustToken and appended to its revocation log.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for revocation
+    /// * `reason` - Why the verification is being revoked
+    /// * `memo` - Optional free-form note for appeals and audits (max 200 characters)
+    /// 
+    /// # Security
+    /// - Only the program authority or an active attestor with the revoke scope can revoke verification
+    /// - Token must currently be verified
+    pub fn revoke_verification(
+        ctx: Context<RevokeVerification>,
+        reason: RevocationReason,
+        memo: Option<String>,
+    ) -> Result<()> {
+        // Security check: Only authority or a scoped attestor can revoke
+        require!(
+            is_authorized(
+                &ctx.accounts.program_state,
+                ctx.accounts.attestor_record.as_ref(),
+                ctx.accounts.authority.key(),
+                Attestor::SCOPE_REVOKE,
+            )?,
+            TrustTokenError::UnauthorizedRevoke
+        );
+        require!(
+            ctx.accounts.trust_token.is_verified,
+            TrustTokenError::AlreadyRevoked
+        );
+        if let Some(ref m) = memo {
+            require!(m.len() <= 200, TrustTokenError::MemoTooLong);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let revoked_by = ctx.accounts.authority.key();
+
+        let trust_token = &mut ctx.accounts.trust_token;
+        trust_token.is_verified = false;
+        trust_token.revoked_at = Some(now);
+        trust_token.revoked_by = Some(revoked_by);
+
+        append_revocation_entry(
+            &mut ctx.accounts.revocation_log,
+            trust_token.mint,
+            RevocationEntry {
+                action: RevocationAction::Revoked,
+                reason: Some(reason),
+                memo,
+                actor: revoked_by,
+                timestamp: now,
+            },
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        emit!(VerificationRevoked {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            reason,
+            revoked_by,
+            timestamp: now,
+        });
+
+        msg!(
+            "Verification revoked for TrustToken: {} (reason: {:?})",
+            trust_token.mint,
+            reason
+        );
+        Ok(())
+    }
+
+    /// Restore verification status of a TrustToken
+    /// This allows the authority to restore a user's verified status.
+    /// The restoration is appended to the token's revocation log.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for restoration
+    /// * `memo` - Optional free-form note for appeals and audits (max 200 characters)
+    /// 
+    /// # Security
+    /// - Only the program authority or an active attestor with the restore scope can restore verification
+    /// - Token must currently be revoked
+    pub fn restore_verification(
+        ctx: Context<RestoreVerification>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        // Security check: Only authority or a scoped attestor can restore
+        require!(
+            is_authorized(
+                &ctx.accounts.program_state,
+                ctx.accounts.attestor_record.as_ref(),
+                ctx.accounts.authority.key(),
+                Attestor::SCOPE_RESTORE,
+            )?,
+            TrustTokenError::UnauthorizedRestore
+        );
+        require!(
+            !ctx.accounts.trust_token.is_verified,
+            TrustTokenError::NotRevoked
+        );
+        if let Some(ref m) = memo {
+            require!(m.len() <= 200, TrustTokenError::MemoTooLong);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let restored_by = ctx.accounts.authority.key();
+
+        let trust_token = &mut ctx.accounts.trust_token;
+        trust_token.is_verified = true;
+        trust_token.revoked_at = None;
+        trust_token.revoked_by = None;
+
+        append_revocation_entry(
+            &mut ctx.accounts.revocation_log,
+            trust_token.mint,
+            RevocationEntry {
+                action: RevocationAction::Restored,
+                reason: None,
+                memo,
+                actor: restored_by,
+                timestamp: now,
+            },
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        emit!(VerificationRestored {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            restored_by,
+            timestamp: now,
+        });
+
+        msg!("Verification restored for TrustToken: {}", trust_token.mint);
+        Ok(())
+    }
+
+    /// Set the verification level of a TrustToken
+    /// Upgrades or downgrades the tier once the holder has passed (or lost) the
+    /// checks that tier requires. Downstream programs can gate features by level.
+    /// The level is also written into the token's metadata: under the `level` key
+    /// for Token-2022 TrustTokens, and through `uri` (pointing at JSON that names
+    /// the new level) for Metaplex TrustTokens, whose metadata has no such field.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `level` - The new verification level
+    /// * `uri` - New metadata URI reflecting the level (required for Metaplex TrustTokens)
+    /// 
+    /// # Security
+    /// - Only the program authority can change verification levels
+    /// - The program PDA signs as the metadata update authority
+    pub fn set_verification_level(
+        ctx: Context<SetVerificationLevel>,
+        level: VerificationLevel,
+        uri: Option<String>,
+    ) -> Result<()> {
+        // Security check: Only authority can change levels
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedLevelChange
+        );
+        if let Some(ref uri) = uri {
+            require!(uri.len() <= 200, TrustTokenError::UriTooLong);
+        }
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+        let is_token_2022 = *ctx.accounts.mint.owner == token_2022::ID;
+        require!(
+            is_token_2022 || uri.is_some(),
+            TrustTokenError::MissingMetadataUri
+        );
+        write_token_metadata(
+            TokenMetadataWrite {
+                payer: ctx.accounts.authority.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                metadata: ctx.accounts.metadata.as_deref().cloned(),
+                token_metadata_program: ctx
+                    .accounts
+                    .token_metadata_program
+                    .as_ref()
+                    .map(|program| program.to_account_info()),
+                token_2022_program: ctx.accounts.token_2022_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            level,
+            uri,
+            signer_seeds,
+        )?;
+
+        let trust_token = &mut ctx.accounts.trust_token;
+        let old_level = trust_token.level;
+        trust_token.level = level;
+
+        emit!(VerificationLevelChanged {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            old_level,
+            new_level: level,
+            changed_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Verification level for TrustToken {} changed: {:?} → {:?}",
+            trust_token.mint,
+            old_level,
+            level
+        );
+        Ok(())
+    }
+
+    /// Update the metadata URI of a TrustToken
+    /// Used to refresh the off-chain metadata after the verification level or status
+    /// changes, so wallets and marketplaces display the current state.
+    /// Metaplex TrustTokens keep their name, symbol, creators and collection;
+    /// Token-2022 TrustTokens get their URI field (and `level` key) rewritten on the mint.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `uri` - The new metadata URI
+    /// 
+    /// # Security
+    /// - Only the program authority can update metadata
+    /// - The program PDA signs as the metadata update authority
+    pub fn update_token_metadata(ctx: Context<UpdateTokenMetadata>, uri: String) -> Result<()> {
+        // Security check: Only authority can update metadata
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.program_state.authority,
+            TrustTokenError::UnauthorizedConfig
+        );
+        require!(uri.len() <= 200, TrustTokenError::UriTooLong);
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+
+        write_token_metadata(
+            TokenMetadataWrite {
+                payer: ctx.accounts.authority.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                metadata: ctx.accounts.metadata.as_deref().cloned(),
+                token_metadata_program: ctx
+                    .accounts
+                    .token_metadata_program
+                    .as_ref()
+                    .map(|program| program.to_account_info()),
+                token_2022_program: ctx.accounts.token_2022_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.accounts.trust_token.level,
+            Some(uri.clone()),
+            signer_seeds,
+        )?;
+
+        let trust_token = &ctx.accounts.trust_token;
+        emit!(TokenMetadataUpdated {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            uri,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Metadata updated for TrustToken: {}", trust_token.mint);
+        Ok(())
+    }
+
+    /// Renew the verification of a TrustToken
+    /// Restarts the validity window from now using the current verification period,
+    /// typically after the holder completes a periodic re-KYC.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for renewal
+    /// 
+    /// # Security
+    /// - Only the program authority or an active attestor with the renew scope can renew
+    /// - Revoked tokens stay revoked; renewal only moves the expiry
+    pub fn renew_verification(ctx: Context<RenewVerification>) -> Result<()> {
+        // Security check: Only authority or a scoped attestor can renew
+        require!(
+            is_authorized(
+                &ctx.accounts.program_state,
+                ctx.accounts.attestor_record.as_ref(),
+                ctx.accounts.authority.key(),
+                Attestor::SCOPE_RENEW,
+            )?,
+            TrustTokenError::UnauthorizedRenew
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let trust_token = &mut ctx.accounts.trust_token;
+        trust_token.expires_at = ctx.accounts.program_state.verification_expiry(now)?;
+
+        emit!(VerificationRenewed {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            expires_at: trust_token.expires_at,
+            renewed_by: ctx.accounts.authority.key(),
+            timestamp: now,
+        });
+
+        msg!(
+            "Verification renewed for TrustToken: {} (expires: {:?})",
+            trust_token.mint,
+            trust_token.expires_at
+        );
+        Ok(())
+    }
+
+    /// Verify that a TrustToken is still bound to its original owner
+    /// This checks if the token is in the correct wallet and hasn't been transferred.
+    /// If the token has been moved, it can be burned by the authority.
+    /// Binding and verification are separate: an expired verification still passes,
+    /// but is reported as unverified in the `SoulboundVerified` event.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for verification
+    /// 
+    /// # Returns
+    /// - Ok(()) if the token is still bound to the original owner
+    /// - Error if the token has been transferred
+    pub fn verify_soulbound(ctx: Context<VerifySoulbound>) -> Result<()> {
+        let trust_token = &ctx.accounts.trust_token;
+        let token_account = &ctx.accounts.token_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Check if the token account owner matches the original TrustToken owner
+        require!(
+            token_account.owner == trust_token.owner,
+            TrustTokenError::TokenTransferred
+        );
+
+        // Check if the token account is frozen (should always be frozen for soulbound),
+        // unless the mint is a Token-2022 NonTransferable mint, which cannot move at all
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let is_non_transferable = *mint_info.owner == token_2022::ID
+            && get_mint_extension_data::<NonTransferable>(&mint_info).is_ok();
+        require!(
+            token_account.is_frozen() || is_non_transferable,
+            TrustTokenError::TokenNotFrozen
+        );
+
+        // Check if the token account has exactly 1 token
+        require!(
+            token_account.amount == 1,
+            TrustTokenError::InvalidTokenAmount
+        );
+
+        emit!(SoulboundVerified {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            is_verified: trust_token.is_verified_at(now),
+            timestamp: now,
+        });
+
+        msg!("TrustToken {} is properly soulbound to {}", trust_token.mint, trust_token.owner);
+        Ok(())
+    }
+
+    /// Look up whether a wallet holds a verified TrustToken
+    /// Resolves the wallet's identity record and returns the verification status
+    /// of the TrustToken it links to. Other programs can CPI into this and read
+    /// the boolean from the return data.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the wallet and its identity record
+    /// 
+    /// # Returns
+    /// - `true` if the wallet's TrustToken is verified and not expired, `false` otherwise
+    /// - Error if the wallet has never minted a TrustToken
+    pub fn lookup_identity(ctx: Context<LookupIdentity>) -> Result<bool> {
+        let trust_token = &ctx.accounts.trust_token;
+        let is_verified = trust_token.is_verified_at(Clock::get()?.unix_timestamp);
+
+        msg!(
+            "Identity {} → TrustToken {} (verified: {})",
+            ctx.accounts.identity.owner,
+            trust_token.mint,
+            is_verified
+        );
+        Ok(is_verified)
+    }
+
+    /// Check whether a TrustToken is verified for a given wallet
+    /// Read-only; other programs can CPI into this and read the boolean from the
+    /// return data. Programs that already load the TrustToken account can call
+    /// `require_verified` directly instead.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing the TrustToken to check
+    /// * `owner` - The wallet expected to hold the TrustToken
+    /// 
+    /// # Returns
+    /// - `true` if `owner` holds the TrustToken and it is verified and not expired, `false` otherwise
+    pub fn check_verified(ctx: Context<CheckVerified>, owner: Pubkey) -> Result<bool> {
+        let trust_token = &ctx.accounts.trust_token;
+        let is_verified = require_verified(trust_token, owner, &Clock::get()?).is_ok();
+
+        msg!(
+            "TrustToken {} for {} (verified: {})",
+            trust_token.mint,
+            owner,
+            is_verified
+        );
+        Ok(is_verified)
+    }
+
+    /// Burn a TrustToken that has been transferred (violating soulbound property)
+    /// This can only be called by the program authority as a security measure.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for burning
+    /// 
+    /// # Security
+    /// - Only the program authority or an active attestor with the burn scope can burn tokens
+    /// - This is used to enforce the soulbound property
+    /// - The thaw and burn are signed by the `mint_authority` PDA as the token account's delegate
+    pub fn burn_transferred_token(ctx: Context<BurnTransferredToken>) -> Result<()> {
+        // Security check: Only authority or a scoped attestor can burn
+        require!(
+            is_authorized(
+                &ctx.accounts.program_state,
+                ctx.accounts.attestor_record.as_ref(),
+                ctx.accounts.authority.key(),
+                Attestor::SCOPE_BURN,
+            )?,
+            TrustTokenError::UnauthorizedBurn
+        );
+
+        let trust_token = &ctx.accounts.trust_token;
+        let token_account = &ctx.accounts.token_account;
+
+        // Verify that the token has indeed been transferred (not in original owner's wallet)
+        require!(
+            token_account.owner != trust_token.owner,
+            TrustTokenError::TokenNotTransferred
+        );
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+
+        // Frozen token accounts cannot be burned, so thaw through the master edition first
+        if token_account.is_frozen() {
+            let thaw_cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                ThawDelegatedAccount {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    delegate: ctx.accounts.mint_authority.to_account_info(),
+                    token_account: ctx.accounts.token_account.to_account_info(),
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            thaw_delegated_account(thaw_cpi_context)?;
+        }
+
+        // Burn the token
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        burn(cpi_context, 1)?;
+
+        emit!(TransferredTokenBurned {
+            mint: trust_token.mint,
+            original_owner: trust_token.owner,
+            holder: token_account.owner,
+            burned_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.total_burned = program_state
+            .total_burned
+            .checked_add(1)
+            .ok_or(TrustTokenError::Overflow)?;
+
+        msg!("Burned transferred TrustToken: {} (was transferred from {})", 
+            trust_token.mint, trust_token.owner);
+        Ok(())
+    }
+
+    /// Report a TrustToken that has left its owner's wallet
+    /// Permissionless counterpart to `burn_transferred_token`: anyone who finds the
+    /// token in a wallet other than its owner's can call this. The TrustToken is marked
+    /// compromised and unverified, the report is appended to its revocation log, and the
+    /// reporter is paid the bounty from the fee vault (or whatever the vault can spare).
+    /// 
+    /// Neither token flavour can leave its owner's wallet through its token program:
+    /// `mint` tokens stay frozen under their master edition and `mint_v2` tokens are
+    /// NonTransferable. Both guarantees live in other programs, so this is the backstop
+    /// should either fail: it takes the holding account from either token program and
+    /// flags the TrustToken without waiting for the authority to notice.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the report
+    /// 
+    /// # Security
+    /// - The token account holding the token is the proof; it must belong to someone else
+    /// - Each TrustToken can only be reported once, so the bounty is paid at most once
+    /// - The vault never drops below its rent-exempt minimum
+    pub fn report_transferred_token(ctx: Context<ReportTransferredToken>) -> Result<()> {
+        let token_account = &ctx.accounts.token_account;
+        require!(
+            token_account.owner != ctx.accounts.trust_token.owner && token_account.amount == 1,
+            TrustTokenError::TokenNotTransferred
+        );
+        require!(
+            ctx.accounts.trust_token.compromised_at.is_none(),
+            TrustTokenError::AlreadyReported
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let reporter = ctx.accounts.reporter.key();
+        let holder = token_account.owner;
+
+        let trust_token = &mut ctx.accounts.trust_token;
+        trust_token.is_verified = false;
+        trust_token.revoked_at = Some(now);
+        trust_token.revoked_by = Some(reporter);
+        trust_token.compromised_at = Some(now);
+
+        append_revocation_entry(
+            &mut ctx.accounts.revocation_log,
+            trust_token.mint,
+            RevocationEntry {
+                action: RevocationAction::Revoked,
+                reason: Some(RevocationReason::SoulboundViolation),
+                memo: None,
+                actor: reporter,
+                timestamp: now,
+            },
+            &ctx.accounts.reporter,
+            &ctx.accounts.system_program,
+        )?;
+
+        // Pay the bounty, capped at what the vault holds above its rent-exempt minimum
+        let fee_vault = ctx.accounts.fee_vault.to_account_info();
+        let bounty = ctx
+            .accounts
+            .program_state
+            .report_payout(fee_vault.lamports(), Rent::get()?.minimum_balance(0));
+        if bounty > 0 {
+            let program_state_key = ctx.accounts.program_state.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"fee_vault",
+                program_state_key.as_ref(),
+                &[ctx.bumps.fee_vault],
+            ]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: fee_vault,
+                        to: ctx.accounts.reporter.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                bounty,
+            )?;
+        }
+
+        emit!(TransferredTokenReported {
+            mint: trust_token.mint,
+            owner: trust_token.owner,
+            holder,
+            reporter,
+            bounty,
+            timestamp: now,
+        });
+
+        msg!(
+            "TrustToken {} reported in wallet {} | Bounty paid: {} lamports",
+            trust_token.mint,
+            holder,
+            bounty
+        );
+        Ok(())
+    }
+
+    /// Burn the caller's own TrustToken and give up their identity
+    /// Burns the NFT, closes the token account, the TrustToken account and the identity
+    /// record, returning all rent to the owner. Works for both `mint` and `mint_v2` tokens.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for burning
+    /// 
+    /// # Security
+    /// - Only the wallet that owns the TrustToken can burn it
+    /// - An open recovery request for the identity is closed, refunding its guardian
+    /// - Burning a revoked or reported token leaves a tombstone, after which the wallet can
+    ///   only mint again with approval, so a fresh mint cannot shed a revocation
+    /// - Frozen Metaplex tokens are thawed by the `mint_authority` PDA as the account's delegate
+    /// - A token already burned directly through Token-2022 only has its accounts closed
+    /// - The revocation log is keyed by mint and survives the burn
+    pub fn burn_own_token(ctx: Context<BurnOwnToken>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // Security check: A revoked or reported token leaves a tombstone behind, so that
+        // giving up the identity does not also give up the revocation
+        let trust_token = &ctx.accounts.trust_token;
+        if !trust_token.is_verified || trust_token.compromised_at.is_some() {
+            let tombstone = ctx
+                .accounts
+                .tombstone
+                .as_mut()
+                .ok_or(TrustTokenError::MissingTombstone)?;
+            tombstone.owner = trust_token.owner;
+            tombstone.mint = trust_token.mint;
+            tombstone.revoked_at = trust_token.revoked_at;
+            tombstone.compromised_at = trust_token.compromised_at;
+            tombstone.burned_at = now;
+        }
+
+        // An open recovery would otherwise outlive the identity it targets
+        close_recovery_request(
+            &ctx.accounts.recovery_request,
+            ctx.accounts.recovery_initiator.as_ref(),
+        )?;
+
+        let program_state_key = ctx.accounts.program_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"mint_authority",
+            program_state_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]];
+
+        // Frozen token accounts cannot be burned, so thaw through the master edition first
+        if ctx.accounts.token_account.is_frozen() {
+            let (metadata, master_edition, token_metadata_program) = match (
+                ctx.accounts.metadata.as_ref(),
+                ctx.accounts.master_edition.as_ref(),
+                ctx.accounts.token_metadata_program.as_ref(),
+            ) {
+                (Some(metadata), Some(master_edition), Some(program)) => {
+                    (metadata, master_edition, program)
+                }
+                _ => return err!(TrustTokenError::MissingMetadataAccounts),
+            };
+
+            let thaw_cpi_context = CpiContext::new_with_signer(
+                token_metadata_program.to_account_info(),
+                ThawDelegatedAccount {
+                    metadata: metadata.to_account_info(),
+                    delegate: ctx.accounts.mint_authority.to_account_info(),
+                    token_account: ctx.accounts.token_account.to_account_info(),
+                    edition: master_edition.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            thaw_delegated_account(thaw_cpi_context)?;
+        }
+
+        // Burn the token, then close the now-empty token account. NonTransferable
+        // tokens can be burned through Token-2022 directly, leaving nothing to burn here.
+        if ctx.accounts.token_account.amount > 0 {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            );
+            token_interface::burn(cpi_context, 1)?;
+        }
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        token_interface::close_account(cpi_context)?;
+
+        // The TrustToken and identity accounts are closed by their `close` constraints
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.total_burned = program_state
+            .total_burned
+            .checked_add(1)
+            .ok_or(TrustTokenError::Overflow)?;
+
+        emit!(TrustTokenBurned {
+            mint: ctx.accounts.trust_token.mint,
+            owner: ctx.accounts.owner.key(),
+            total_burned: program_state.total_burned,
+            timestamp: now,
+        });
+
+        msg!(
+            "TrustToken {} burned by its owner {}",
+            ctx.accounts.trust_token.mint,
+            ctx.accounts.owner.key()
+        );
+        Ok(())
+    }
+
+    /// Migrate an identity to a new wallet
+    /// Recovers a TrustToken stranded in a wallet whose key was lost: the old token
+    /// is burned by the program PDA and a replacement of the same standard (Metaplex or
+    /// Token-2022) is minted to the new wallet. `minted_at`, level, verification status and
+    /// guardians carry over, the old identity and TrustToken accounts are closed, and an
+    /// `IdentityLink` records old → new so reputation linked to the old wallet can be
+    /// followed. The replacement's revocation log points back at the old mint. A guardian
+    /// recovery still open for the old wallet is closed along with it.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the migration
+    /// * `name` - The name of the replacement NFT
+    /// * `symbol` - The symbol of the replacement NFT
+    /// * `uri` - The metadata URI of the replacement NFT
+    /// 
+    /// # Security
+    /// - Only the program authority or an active attestor with the migrate scope can approve
+    /// - The new wallet must sign, and must not already hold an identity
+    /// - Mint and burn counters are unchanged, since no identity is created or destroyed
+    /// - An open recovery request is closed to the guardian that opened it, so it cannot
+    ///   outlive the identity it was meant to recover
+    pub fn migrate_identity(
+        ctx: Context<MigrateIdentity>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        // Security check: Only authority or a scoped attestor can approve migrations
+        require!(
+            is_authorized(
+                &ctx.accounts.migration.program_state,
+                ctx.accounts.attestor_record.as_ref(),
+                ctx.accounts.approver.key(),
+                Attestor::SCOPE_MIGRATE,
+            )?,
+            TrustTokenError::UnauthorizedMigration
+        );
+
+        close_recovery_request(
+            &ctx.accounts.recovery_request,
+            ctx.accounts.recovery_initiator.as_ref(),
+        )?;
+
+        migrate_trust_token(
+            &mut ctx.accounts.migration,
+            &ctx.bumps.migration,
+            ctx.accounts.approver.key(),
+            name,
+            symbol,
+            uri,
+        )
+    }
+
+    /// Set the guardians that can recover the caller's identity
+    /// Replaces the whole guardian set. Pass an empty list and a threshold of 0
+    /// to turn guardian recovery off.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the update
+    /// * `guardians` - Up to `Identity::MAX_GUARDIANS` distinct guardian wallets
+    /// * `threshold` - Guardian approvals needed to recover
+    /// 
+    /// # Security
+    /// - Only the identity's owner can change its guardians
+    /// - The owner cannot be their own guardian
+    /// - A pending recovery is re-counted against the new guardians: it loses its
+    ///   approval if it falls below the threshold, and restarts the delay if it reaches it
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let identity = &mut ctx.accounts.identity;
+
+        require!(
+            guardians.len() <= Identity::MAX_GUARDIANS,
+            TrustTokenError::TooManyGuardians
+        );
+        require!(
+            (threshold as usize) <= guardians.len() && (threshold > 0 || guardians.is_empty()),
+            TrustTokenError::InvalidGuardianThreshold
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                *guardian != identity.owner && !guardians[..i].contains(guardian),
+                TrustTokenError::InvalidGuardian
+            );
+        }
+
+        identity.guardians = guardians;
+        identity.guardian_threshold = threshold;
+
+        // A pending recovery is re-checked against the new guardians, restarting its delay
+        let now = Clock::get()?.unix_timestamp;
+        let recovery_info = ctx.accounts.recovery_request.to_account_info();
+        if recovery_info.owner == &crate::ID && !recovery_info.data_is_empty() {
+            let mut data = recovery_info.try_borrow_mut_data()?;
+            let mut recovery_request = RecoveryRequest::try_deserialize(&mut &data[..])?;
+            recovery_request.refresh_approval(identity, now);
+            recovery_request.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(GuardiansUpdated {
+            owner: identity.owner,
+            guardians: identity.guardians.clone(),
+            threshold,
+            timestamp: now,
+        });
+
+        msg!(
+            "Guardians for {} set: {} of {}",
+            identity.owner,
+            threshold,
+            identity.guardians.len()
+        );
+        Ok(())
+    }
+
+    /// Open a guardian recovery of an identity to a new wallet
+    /// Counts as the calling guardian's approval.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed to open the request
+    /// * `new_owner` - The wallet the identity should move to
+    /// 
+    /// # Security
+    /// - Only a guardian of the identity can open a recovery
+    /// - Only one recovery per identity can be pending at a time; a request that stalls
+    ///   below the threshold can be withdrawn with `expire_recovery`
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>, new_owner: Pubkey) -> Result<()> {
+        let identity = &ctx.accounts.identity;
+        let guardian = ctx.accounts.guardian.key();
+
+        require!(
+            identity.guardians.contains(&guardian),
+            TrustTokenError::NotGuardian
+        );
+        require!(
+            new_owner != identity.owner,
+            TrustTokenError::MigrationToSameWallet
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let recovery_request = &mut ctx.accounts.recovery_request;
+        recovery_request.owner = identity.owner;
+        recovery_request.new_owner = new_owner;
+        recovery_request.initiated_by = guardian;
+        recovery_request.approvals = vec![guardian];
+        recovery_request.initiated_at = now;
+        recovery_request.approved_at = None;
+        recovery_request.refresh_approval(identity, now);
+
+        emit!(RecoveryInitiated {
+            owner: identity.owner,
+            new_owner,
+            guardian,
+            timestamp: now,
+        });
+
+        msg!(
+            "Recovery of {} to {} initiated by guardian {}",
+            identity.owner,
+            new_owner,
+            guardian
+        );
+        Ok(())
+    }
+
+    /// Approve a pending guardian recovery
+    /// Once the threshold is reached the delay starts, after which the new wallet
+    /// can execute the recovery with `recover_with_guardians`.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the approval
+    /// 
+    /// # Security
+    /// - Only a guardian of the identity can approve, and only once
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        let identity = &ctx.accounts.identity;
+        let guardian = ctx.accounts.guardian.key();
+
+        require!(
+            identity.guardians.contains(&guardian),
+            TrustTokenError::NotGuardian
+        );
+
+        let recovery_request = &mut ctx.accounts.recovery_request;
+        require!(
+            !recovery_request.approvals.contains(&guardian),
+            TrustTokenError::AlreadyApproved
+        );
+        recovery_request.approvals.push(guardian);
+
+        let now = Clock::get()?.unix_timestamp;
+        let approvals = count_guardian_approvals(identity, recovery_request);
+        recovery_request.refresh_approval(identity, now);
+
+        emit!(RecoveryApproved {
+            owner: identity.owner,
+            guardian,
+            approvals,
+            threshold: identity.guardian_threshold,
+            timestamp: now,
+        });
+
+        msg!(
+            "Recovery of {} approved by guardian {} ({}/{})",
+            identity.owner,
+            guardian,
+            approvals,
+            identity.guardian_threshold
+        );
+        Ok(())
+    }
+
+    /// Cancel a pending guardian recovery
+    /// Lets the owner stop a recovery they did not ask for, as long as they still hold their key.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the cancellation
+    /// 
+    /// # Security
+    /// - Only the identity's owner can cancel
+    /// - Rent is returned to the guardian that opened the request
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        let recovery_request = &ctx.accounts.recovery_request;
+
+        emit!(RecoveryCancelled {
+            owner: recovery_request.owner,
+            new_owner: recovery_request.new_owner,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Recovery of {} to {} cancelled by owner",
+            recovery_request.owner,
+            recovery_request.new_owner
+        );
+        Ok(())
+    }
+
+    /// Withdraw a guardian recovery that has not reached its threshold
+    /// Frees the identity for a new request, so a guardian that opened a recovery
+    /// nobody else supports cannot block recovery forever. The guardian that opened
+    /// the request can withdraw it at any time, e.g. to correct the new wallet.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed to withdraw the request
+    /// 
+    /// # Security
+    /// - Only a guardian of the identity can withdraw a request
+    /// - Other guardians must wait `RecoveryRequest::TIMEOUT` after it was opened
+    /// - Approvals are recounted against the current guardian set and threshold;
+    ///   an approved request can only be cancelled by the owner or executed
+    /// - Rent is returned to the guardian that opened the request
+    pub fn expire_recovery(ctx: Context<ExpireRecovery>) -> Result<()> {
+        let identity = &ctx.accounts.identity;
+        let recovery_request = &ctx.accounts.recovery_request;
+        let guardian = ctx.accounts.guardian.key();
+
+        require!(
+            identity.guardians.contains(&guardian),
+            TrustTokenError::NotGuardian
+        );
+        require!(
+            !recovery_request.is_approved(identity),
+            TrustTokenError::RecoveryAlreadyApproved
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let expires_at = recovery_request
+            .initiated_at
+            .checked_add(RecoveryRequest::TIMEOUT)
+            .ok_or(TrustTokenError::Overflow)?;
+        require!(
+            guardian == recovery_request.initiated_by || now >= expires_at,
+            TrustTokenError::RecoveryNotExpired
+        );
+
+        emit!(RecoveryExpired {
+            owner: recovery_request.owner,
+            new_owner: recovery_request.new_owner,
+            guardian,
+            timestamp: now,
+        });
+
+        msg!(
+            "Recovery of {} to {} withdrawn by guardian {}",
+            recovery_request.owner,
+            recovery_request.new_owner,
+            guardian
+        );
+        Ok(())
+    }
+
+    /// Execute an approved guardian recovery
+    /// Migrates the identity to the request's new wallet exactly like `migrate_identity`,
+    /// once enough guardians approved and the delay has passed.
+    /// 
+    /// # Arguments
+    /// * `ctx` - The context containing all accounts needed for the recovery
+    /// * `name` - The name of the replacement NFT
+    /// * `symbol` - The symbol of the replacement NFT
+    /// * `uri` - The metadata URI of the replacement NFT
+    /// 
+    /// # Security
+    /// - Approvals are recounted against the current guardian set and threshold
+    /// - The owner had `RecoveryRequest::DELAY` to cancel after the threshold was reached
+    /// - Only the new wallet named in the request can execute it
+    pub fn recover_with_guardians(
+        ctx: Context<RecoverWithGuardians>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let identity = &ctx.accounts.migration.old_identity;
+        let recovery_request = &ctx.accounts.recovery_request;
+
+        require!(
+            recovery_request.is_approved(identity),
+            TrustTokenError::RecoveryNotApproved
+        );
+        require!(
+            recovery_request.is_ready(identity, Clock::get()?.unix_timestamp)?,
+            TrustTokenError::RecoveryDelayNotElapsed
+        );
+
+        migrate_trust_token(
+            &mut ctx.accounts.migration,
+            &ctx.bumps.migration,
+            ctx.accounts.recovery_request.key(),
+            name,
+            symbol,
+            uri,
+        )
+    }
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+/// Program state account that stores global configuration
+#[account]
+pub struct ProgramState {
+    /// The authority that can mint and manage TrustTokens
+    pub authority: Pubkey,
+    /// Total number of TrustTokens minted
+    pub total_minted: u64,
+    /// Who is allowed to mint TrustTokens
+    pub mint_policy: MintPolicy,
+    /// Proposed successor authority awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+    /// How long a verification stays valid, in seconds (`None` = never expires)
+    pub verification_period: Option<i64>,
+    /// Total number of TrustTokens burned
+    pub total_burned: u64,
+    /// Lamports paid from the fee vault for each valid soulbound violation report
+    pub report_bounty: u64,
+}
+
+impl ProgramState {
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // total_minted
+        1 + // mint_policy (enum)
+        1 + 32 + // pending_authority (Option<Pubkey>)
+        1 + 8 + // verification_period (Option<i64>)
+        8 + // total_burned
+        8; // report_bounty
+
+    /// Expiry for a verification granted at `from`, based on the current period
+    pub fn verification_expiry(&self, from: i64) -> Result<Option<i64>> {
+        self.verification_period
+            .map(|period| from.checked_add(period).ok_or(TrustTokenError::Overflow.into()))
+            .transpose()
+    }
+
+    /// Bounty paid from a fee vault holding `vault_lamports`
+    /// Capped so the vault keeps `vault_floor`, its rent-exempt minimum.
+    pub fn report_payout(&self, vault_lamports: u64, vault_floor: u64) -> u64 {
+        self.report_bounty.min(vault_lamports.saturating_sub(vault_floor))
+    }
+}
+
+/// TrustToken account that stores NFT-specific data
+/// This account is created for each minted TrustToken and stores verification status
+#[account]
+pub struct TrustToken {
+    /// The owner of this TrustToken
+    pub owner: Pubkey,
+    /// The mint address of this TrustToken NFT
+    pub mint: Pubkey,
+    /// Whether this token represents a verified user
+    pub is_verified: bool,
+    /// Timestamp when the token was minted
+    pub minted_at: i64,
+    /// Timestamp when the verification lapses (if it expires)
+    pub expires_at: Option<i64>,
+    /// Verification tier the holder has reached
+    pub level: VerificationLevel,
+    /// When verification was last revoked (cleared on restore)
+    pub revoked_at: Option<i64>,
+    /// Who last revoked verification (cleared on restore)
+    pub revoked_by: Option<Pubkey>,
+    /// When the token was reported as having left the owner's wallet
+    pub compromised_at: Option<i64>,
+}
+
+impl TrustToken {
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // mint
+        1 + // is_verified
+        8 + // minted_at
+        1 + 8 + // expires_at (Option<i64>)
+        1 + // level (enum)
+        1 + 8 + // revoked_at (Option<i64>)
+        1 + 32 + // revoked_by (Option<Pubkey>)
+        1 + 8; // compromised_at (Option<i64>)
+
+    /// Whether the verification has lapsed at `now`
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expiry| now >= expiry)
+    }
+
+    /// Whether the token counts as verified at `now`
+    pub fn is_verified_at(&self, now: i64) -> bool {
+        self.is_verified && !self.is_expired(now)
+    }
+
+    /// Derive the TrustToken data account address for a mint
+    pub fn address(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"trust_token", mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Identity record that links a wallet to its single TrustToken
+/// Seeded by the owner's wallet, so each wallet has exactly one deterministic
+/// address that other programs can resolve.
+#[account]
+pub struct Identity {
+    /// The wallet this identity belongs to
+    pub owner: Pubkey,
+    /// The mint address of the wallet's TrustToken NFT
+    pub mint: Pubkey,
+    /// The TrustToken data account for that mint
+    pub trust_token: Pubkey,
+    /// Timestamp when the identity was created (kept across migrations)
+    pub created_at: i64,
+    /// The wallet this identity was migrated from, if any
+    pub migrated_from: Option<Pubkey>,
+    /// Wallets that can jointly recover this identity to a new wallet
+    pub guardians: Vec<Pubkey>,
+    /// Guardian approvals needed to recover (0 when no guardians are set)
+    pub guardian_threshold: u8,
+}
+
+impl Identity {
+    /// Maximum number of guardians per identity
+    pub const MAX_GUARDIANS: usize = 5;
+
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // mint
+        32 + // trust_token
+        8 + // created_at
+        1 + 32 + // migrated_from (Option<Pubkey>)
+        4 + Self::MAX_GUARDIANS * 32 + // guardians (Vec<Pubkey>)
+        1; // guardian_threshold
+
+    /// Derive the identity record address for a wallet
+    pub fn address(owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"identity", owner.as_ref()], &crate::ID)
+    }
+}
+
+/// Record left behind when a wallet burns a revoked or reported TrustToken
+/// Seeded by the wallet. While it exists, minting to that wallet needs the
+/// authority's or an attestor's approval whatever the minting policy.
+#[account]
+pub struct Tombstone {
+    /// The wallet that burned the token
+    pub owner: Pubkey,
+    /// The burned TrustToken mint (its revocation log is still keyed by it)
+    pub mint: Pubkey,
+    /// When the burned token's verification was revoked, if it was
+    pub revoked_at: Option<i64>,
+    /// When the burned token was reported as transferred, if it was
+    pub compromised_at: Option<i64>,
+    /// Timestamp of the burn
+    pub burned_at: i64,
+}
+
+impl Tombstone {
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // mint
+        1 + 8 + // revoked_at (Option<i64>)
+        1 + 8 + // compromised_at (Option<i64>)
+        8; // burned_at
+}
+
+/// Pending guardian recovery of an identity
+/// Seeded by the identity's current wallet, so each identity has at most one
+/// recovery in flight. The owner can cancel it until it is executed.
+#[account]
+pub struct RecoveryRequest {
+    /// The wallet whose identity is being recovered
+    pub owner: Pubkey,
+    /// The wallet the identity will move to
+    pub new_owner: Pubkey,
+    /// The guardian that opened the request (paid its rent)
+    pub initiated_by: Pubkey,
+    /// Guardians that have approved, in order
+    pub approvals: Vec<Pubkey>,
+    /// Timestamp the request was opened
+    pub initiated_at: i64,
+    /// Timestamp the approval threshold was reached, starting the delay
+    pub approved_at: Option<i64>,
+}
+
+impl RecoveryRequest {
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // new_owner
+        32 + // initiated_by
+        4 + Identity::MAX_GUARDIANS * 32 + // approvals (Vec<Pubkey>)
+        8 + // initiated_at
+        1 + 8; // approved_at (Option<i64>)
+
+    /// Time the owner has to cancel after the threshold is reached (3 days)
+    pub const DELAY: i64 = 3 * 24 * 60 * 60;
+
+    /// Time after which any guardian can withdraw a request still below the threshold (7 days)
+    pub const TIMEOUT: i64 = 7 * 24 * 60 * 60;
+
+    /// Whether the identity's current guardians have reached its threshold
+    pub fn is_approved(&self, identity: &Identity) -> bool {
+        identity.guardian_threshold > 0
+            && count_guardian_approvals(identity, self) >= identity.guardian_threshold
+    }
+
+    /// Re-check the threshold after an approval or a guardian change
+    /// The delay starts on the change that reaches the threshold and is
+    /// dropped whenever the request falls below it again.
+    pub fn refresh_approval(&mut self, identity: &Identity, now: i64) {
+        if !self.is_approved(identity) {
+            self.approved_at = None;
+        } else if self.approved_at.is_none() {
+            self.approved_at = Some(now);
+        }
+    }
+
+    /// Whether the request is approved and its delay has passed
+    pub fn is_ready(&self, identity: &Identity, now: i64) -> Result<bool> {
+        let Some(approved_at) = self.approved_at.filter(|_| self.is_approved(identity)) else {
+            return Ok(false);
+        };
+        let ready_at = approved_at
+            .checked_add(Self::DELAY)
+            .ok_or(TrustTokenError::Overflow)?;
+        Ok(now >= ready_at)
+    }
+}
+
+/// Record of an identity moving from one wallet to another
+/// Seeded by the old wallet, so anything still keyed by that wallet (such as
+/// reputation cards it received) can be followed to the identity's new wallet.
+/// A wallet can be migrated away from more than once (A → B, then B → A → C), in
+/// which case the link is overwritten and always points at the latest move.
+#[account]
+pub struct IdentityLink {
+    /// The wallet the identity was migrated from
+    pub old_owner: Pubkey,
+    /// The wallet the identity was migrated to
+    pub new_owner: Pubkey,
+    /// The burned TrustToken mint
+    pub old_mint: Pubkey,
+    /// The replacement TrustToken mint
+    pub new_mint: Pubkey,
+    /// Who approved the migration
+    pub approved_by: Pubkey,
+    /// Timestamp of the migration
+    pub migrated_at: i64,
+}
+
+impl IdentityLink {
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // old_owner
+        32 + // new_owner
+        32 + // old_mint
+        32 + // new_mint
+        32 + // approved_by
+        8; // migrated_at
+
+    /// Derive the identity link address for a migrated wallet
+    /// Holds the wallet's most recent migration
+    pub fn address(old_owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"identity_link", old_owner.as_ref()], &crate::ID)
+    }
+}
+
+/// Attestor account registered by the authority
+/// An attestor is a verification provider that can act for the authority
+/// within the scopes it was granted, until its optional expiry.
+#[account]
+pub struct Attestor {
+    /// The attestor's signing key
+    pub attestor: Pubkey,
+    /// The authority that registered this attestor
+    pub added_by: Pubkey,
+    /// Timestamp when the attestor was registered
+    pub added_at: i64,
+    /// Bitmask of `SCOPE_*` actions this attestor may perform
+    pub scopes: u8,
+    /// Timestamp after which the attestor can no longer act (if set)
+    pub expires_at: Option<i64>,
+}
+
+impl Attestor {
+    /// Calculate the space needed for this account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // attestor
+        32 + // added_by
+        8 + // added_at
+        1 + // scopes
+        1 + 8; // expires_at (Option<i64>)
+
+    /// May co-sign mints under `MintPolicy::AttestorSigned`
+    pub const SCOPE_MINT_APPROVE: u8 = 1 << 0;
+    /// May revoke verification
+    pub const SCOPE_REVOKE: u8 = 1 << 1;
+    /// May restore verification
+    pub const SCOPE_RESTORE: u8 = 1 << 2;
+    /// May burn transferred tokens
+    pub const SCOPE_BURN: u8 = 1 << 3;
+    /// May renew expiring verifications
+    pub const SCOPE_RENEW: u8 = 1 << 4;
+    /// May approve wallet migrations
+    pub const SCOPE_MIGRATE: u8 = 1 << 5;
+    /// Every scope an attestor can hold
+    pub const SCOPE_ALL: u8 = Self::SCOPE_MINT_APPROVE
+        | Self::SCOPE_REVOKE
+        | Self::SCOPE_RESTORE
+        | Self::SCOPE_BURN
+        | Self::SCOPE_RENEW
+        | Self::SCOPE_MIGRATE;
+
+    /// Whether this attestor holds `scope` and has not expired at `now`
+    pub fn allows(&self, scope: u8, now: i64) -> bool {
+        self.scopes & scope == scope && self.expires_at.map_or(true, |expiry| now < expiry)
+    }
+}
+
+/// Append-only log of revocations and restorations for one TrustToken
+/// Grows by one entry per action so appeals and audits can replay the history.
+/// A migrated TrustToken's log links back to the mint it replaced.
+#[account]
+pub struct RevocationLog {
+    /// The mint of the TrustToken this log belongs to
+    pub mint: Pubkey,
+    /// The mint this TrustToken replaced in a migration, whose log holds the earlier history
+    pub previous_mint: Option<Pubkey>,
+    /// Entries in the order they happened
+    pub entries: Vec<RevocationEntry>,
+}
+
+impl RevocationLog {
+    /// Calculate the space needed for a log holding `entries` entries
+    pub const fn space(entries: usize) -> usize {
+        8 + // discriminator
+        32 + // mint
+        1 + 32 + // previous_mint (Option<Pubkey>)
+        4 + entries * RevocationEntry::LEN // entries (Vec<RevocationEntry>)
+    }
+}
+
+/// A single revocation log entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RevocationEntry {
+    /// Whether verification was revoked or restored
+    pub action: RevocationAction,
+    /// Reason code (revocations only)
+    pub reason: Option<RevocationReason>,
+    /// Optional free-form note
+    pub memo: Option<String>,
+    /// Who performed the action
+    pub actor: Pubkey,
+    /// When the action happened
+    pub timestamp: i64,
+}
+
+impl RevocationEntry {
+    pub const LEN: usize = 1 + // action (enum)
+        1 + 1 + // reason (Option<enum>)
+        1 + 4 + 200 + // memo (Option<String> max 200)
+        32 + // actor
+        8; // timestamp
+}
+
+/// Action recorded in a revocation log entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RevocationAction {
+    Revoked,
+    Restored,
+}
+
+/// Reason codes for revoking verification
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RevocationReason {
+    /// Fraudulent activity or documents
+    Fraud,
+    /// Verification documents are no longer valid
+    DocumentsExpired,
+    /// Held pending a compliance or legal review
+    ComplianceHold,
+    /// Holder's key is believed to be compromised
+    KeyCompromised,
+    /// Holder asked for the revocation
+    UserRequest,
+    /// Anything else; explain in the memo
+    Other,
+    /// Token was found outside the holder's wallet by a soulbound violation report
+    SoulboundViolation,
+}
+
+/// Verification tiers, ordered from least to most thoroughly verified
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum VerificationLevel {
+    /// Wallet holds a TrustToken under the current minting policy
+    Basic,
+    /// Identity documents checked ("Verification Plus")
+    Enhanced,
+    /// Registered business entity
+    Business,
+}
+
+impl VerificationLevel {
+    /// Token-2022 additional metadata key holding the level
+    pub const METADATA_KEY: &'static str = "level";
+
+    /// Name of the level as written into token metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationLevel::Basic => "Basic",
+            VerificationLevel::Enhanced => "Enhanced",
+            VerificationLevel::Business => "Business",
+        }
+    }
+}
+
+/// Minting policy for TrustTokens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MintPolicy {
+    /// Any wallet can self-mint
+    Open,
+    /// The program authority must co-sign every mint
+    AuthorityOnly,
+    /// The program authority or a registered attestor must co-sign every mint
+    AttestorSigned,
+}
+
+impl MintPolicy {
+    /// The policy that applies to a wallet
+    /// A tombstoned wallet cannot self-mint, even when minting is open.
+    pub fn for_wallet(self, tombstoned: bool) -> Self {
+        match self {
+            MintPolicy::Open if tombstoned => MintPolicy::AttestorSigned,
+            policy => policy,
+        }
+    }
+}
+
+// ============================================================================
+// Context Structures
+// ============================================================================
+
+/// Context for initializing the TrustToken program
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// The authority that will control the program
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account - stores global configuration
+    /// Seeds ensure only one program state can exist
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramState::LEN,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    pub system_program: Program<'info, System>,
+}
 
 /// Context for minting a TrustToken NFT
 #[derive(Accounts)]
-pub struct MintTrustToken<'info> {
-    /// The user minting the token (pays for everything and receives the NFT)
+pub struct MintTrustToken<'info> {
+    /// The user minting the token (pays for everything and receives the NFT)
+    #[account(mut)]
+    pub minter: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Co-signer approving the mint when the policy is not `Open`
+    pub approver: Option<Signer<'info>>,
+
+    /// Attestor record for the approver, required when an attestor approves
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    /// Program-owned authority PDA
+    /// Holds mint and freeze authority so holders cannot thaw or move their token,
+    /// and metadata update authority so holders cannot rewrite it
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The mint account for the NFT
+    /// Each TrustToken gets a unique mint
+    #[account(
+        init,
+        payer = minter,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account that will hold the NFT
+    /// Uses associated token account for standardization
+    #[account(
+        init_if_needed,
+        payer = minter,
+        associated_token::mint = mint,
+        associated_token::authority = minter,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// TrustToken data account
+    /// Stores verification status and metadata
+    #[account(
+        init,
+        payer = minter,
+        space = TrustToken::LEN,
+        seeds = [b"trust_token", mint.key().as_ref()],
+        bump
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// Identity record for the minter
+    /// `init` fails if the wallet already minted, enforcing one TrustToken per wallet
+    #[account(
+        init,
+        payer = minter,
+        space = Identity::LEN,
+        seeds = [b"identity", minter.key().as_ref()],
+        bump
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// Tombstone left if the minter burned a revoked TrustToken (usually empty)
+    /// CHECK: Validated by seeds; only this program can write to it
+    #[account(seeds = [b"tombstone", minter.key().as_ref()], bump)]
+    pub tombstone: UncheckedAccount<'info>,
+
+    /// Metadata account for the NFT
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Master edition account for the NFT
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// The TrustToken collection mint
+    #[account(
+        seeds = [b"collection_mint", program_state.key().as_ref()],
+        bump
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// Metadata account for the collection NFT
+    /// CHECK: Validated against the collection mint by the Metaplex program
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// Master edition account for the collection NFT
+    /// CHECK: Validated against the collection mint by the Metaplex program
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Context for minting a Token-2022 TrustToken
+#[derive(Accounts)]
+pub struct MintTrustTokenV2<'info> {
+    /// The user minting the token (pays for everything and receives the token)
+    #[account(mut)]
+    pub minter: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    /// Co-signer approving the mint when the policy is not `Open`
+    pub approver: Option<Signer<'info>>,
+
+    /// Attestor record for the approver, required when an attestor approves
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    /// Program-owned authority PDA
+    /// Holds mint, freeze and metadata update authority, and is the permanent delegate
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The new mint account, created and initialized with extensions in the handler
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// The minter's associated token account, created in the handler
+    /// CHECK: Address is validated by the associated token program on creation
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// TrustToken data account
+    #[account(
+        init,
+        payer = minter,
+        space = TrustToken::LEN,
+        seeds = [b"trust_token", mint.key().as_ref()],
+        bump
+    )]
+    pub trust_token: Box<Account<'info, TrustToken>>,
+
+    /// Identity record for the minter
+    /// `init` fails if the wallet already minted, enforcing one TrustToken per wallet
+    #[account(
+        init,
+        payer = minter,
+        space = Identity::LEN,
+        seeds = [b"identity", minter.key().as_ref()],
+        bump
+    )]
+    pub identity: Box<Account<'info, Identity>>,
+
+    /// Tombstone left if the minter burned a revoked TrustToken (usually empty)
+    /// CHECK: Validated by seeds; only this program can write to it
+    #[account(seeds = [b"tombstone", minter.key().as_ref()], bump)]
+    pub tombstone: UncheckedAccount<'info>,
+
+    /// The Token-2022 TrustToken group the new mint joins
+    /// CHECK: Validated by seeds; the token program checks it is an initialized group
+    #[account(
+        mut,
+        seeds = [b"collection_group", program_state.key().as_ref()],
+        bump
+    )]
+    pub collection_group: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for initializing the Token-2022 TrustToken group
+#[derive(Accounts)]
+pub struct InitializeCollectionV2<'info> {
+    /// The program authority (pays for the group mint)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-owned authority PDA, the group's mint and update authority
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The group mint, one per program, created in the handler
+    /// CHECK: Validated by seeds; created and initialized in the handler
+    #[account(
+        mut,
+        seeds = [b"collection_group", program_state.key().as_ref()],
+        bump
+    )]
+    pub collection_group: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for initializing the TrustToken collection NFT
+#[derive(Accounts)]
+pub struct InitializeCollection<'info> {
+    /// The program authority (pays for the collection accounts)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-owned authority PDA
+    /// Holds the collection NFT and is its update authority
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The collection mint, one per program
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"collection_mint", program_state.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// The PDA's token account holding the collection NFT
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = mint_authority,
+    )]
+    pub collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Metadata account for the collection NFT
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// Master edition account for the collection NFT
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Context for setting the minting policy
+#[derive(Accounts)]
+pub struct SetMintPolicy<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for proposing a new authority
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    /// The current program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for accepting a pending authority transfer
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// The proposed authority
+    pub new_authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for migrating the program state account
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// The program authority (pays for the extra space)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account in its old layout
+    /// CHECK: Cannot be deserialized before migration; discriminator and authority are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub program_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating a TrustToken account
+#[derive(Accounts)]
+pub struct MigrateTrustTokenAccount<'info> {
+    /// Whoever pays for the extra space and the identity record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The TrustToken's owner
+    /// CHECK: Compared against the owner stored in the TrustToken
+    pub owner: UncheckedAccount<'info>,
+
+    /// TrustToken account in its old layout
+    /// CHECK: Cannot be deserialized before migration; discriminator and owner are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub trust_token: UncheckedAccount<'info>,
+
+    /// The owner's identity record, created if missing
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Identity::LEN,
+        seeds = [b"identity", owner.key().as_ref()],
+        bump
+    )]
+    pub identity: Account<'info, Identity>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting the verification period
+#[derive(Accounts)]
+pub struct SetVerificationPeriod<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for setting the report bounty
+#[derive(Accounts)]
+pub struct SetReportBounty<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Context for funding the fee vault
+#[derive(Accounts)]
+pub struct FundFeeVault<'info> {
+    /// Whoever is depositing
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-held vault that pays report bounties
+    #[account(
+        mut,
+        seeds = [b"fee_vault", program_state.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for registering an attestor
+#[derive(Accounts)]
+pub struct AddAttestor<'info> {
+    /// The program authority (pays for the attestor record)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The attestor being registered
+    /// CHECK: We only need their public key
+    pub attestor: UncheckedAccount<'info>,
+
+    /// Attestor record
+    #[account(
+        init,
+        payer = authority,
+        space = Attestor::LEN,
+        seeds = [b"attestor", attestor.key().as_ref()],
+        bump
+    )]
+    pub attestor_record: Account<'info, Attestor>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for updating an attestor
+#[derive(Accounts)]
+pub struct UpdateAttestor<'info> {
+    /// The program authority
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Attestor record to update
+    #[account(
+        mut,
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Account<'info, Attestor>,
+}
+
+/// Context for removing an attestor
+#[derive(Accounts)]
+pub struct RemoveAttestor<'info> {
+    /// The program authority (receives the record's rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Attestor record to close
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Account<'info, Attestor>,
+}
+
+/// Context for revoking verification
+#[derive(Accounts)]
+pub struct RevokeVerification<'info> {
+    /// The program authority, or an attestor with the revoke scope
+    /// Pays for growing the revocation log
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Attestor record, required when an attestor signs instead of the authority
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    /// The TrustToken account to revoke
+    #[account(
+        mut,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// Append-only revocation history for this TrustToken
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RevocationLog::space(0),
+        seeds = [b"revocation_log", trust_token.mint.as_ref()],
+        bump
+    )]
+    pub revocation_log: Account<'info, RevocationLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for restoring verification
+#[derive(Accounts)]
+pub struct RestoreVerification<'info> {
+    /// The program authority, or an attestor with the restore scope
+    /// Pays for growing the revocation log
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Attestor record, required when an attestor signs instead of the authority
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    /// The TrustToken account to restore
+    #[account(
+        mut,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// Append-only revocation history for this TrustToken
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RevocationLog::space(0),
+        seeds = [b"revocation_log", trust_token.mint.as_ref()],
+        bump
+    )]
+    pub revocation_log: Account<'info, RevocationLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for setting a verification level
+#[derive(Accounts)]
+pub struct SetVerificationLevel<'info> {
+    /// The program authority (pays for any extra Token-2022 metadata space)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-owned authority PDA, the metadata update authority
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The TrustToken account to update
+    #[account(
+        mut,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// The TrustToken's mint; holds the metadata for Token-2022 TrustTokens
+    /// CHECK: Address is checked against the TrustToken; the owner selects the metadata path
+    #[account(
+        mut,
+        address = trust_token.mint @ TrustTokenError::MetadataMismatch,
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Metaplex metadata account, required for Metaplex TrustTokens
+    #[account(
+        mut,
+        constraint = metadata.mint == trust_token.mint @ TrustTokenError::MetadataMismatch,
+    )]
+    pub metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for updating TrustToken metadata
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    /// The program authority (pays for any extra Token-2022 metadata space)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-owned authority PDA, the metadata update authority
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The TrustToken whose metadata is updated
+    #[account(
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// The TrustToken's mint; holds the metadata for Token-2022 TrustTokens
+    /// CHECK: Address is checked against the TrustToken; the owner selects the metadata path
+    #[account(
+        mut,
+        address = trust_token.mint @ TrustTokenError::MetadataMismatch,
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Metaplex metadata account, required for Metaplex TrustTokens
+    #[account(
+        mut,
+        constraint = metadata.mint == trust_token.mint @ TrustTokenError::MetadataMismatch,
+    )]
+    pub metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for renewing verification
+#[derive(Accounts)]
+pub struct RenewVerification<'info> {
+    /// The program authority, or an attestor with the renew scope
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Attestor record, required when an attestor signs instead of the authority
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    /// The TrustToken account to renew
+    #[account(
+        mut,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+}
+
+/// Context for verifying soulbound status
+#[derive(Accounts)]
+pub struct VerifySoulbound<'info> {
+    /// The TrustToken account to verify
+    #[account(
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// The mint account (SPL Token or Token-2022)
+    #[account(
+        address = trust_token.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// The token account to verify
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = trust_token.owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// The token program that owns the mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Context for looking up a wallet's identity
+#[derive(Accounts)]
+pub struct LookupIdentity<'info> {
+    /// The wallet being looked up
+    /// CHECK: Only used as a seed for the identity record
+    pub owner: UncheckedAccount<'info>,
+
+    /// The wallet's identity record
+    #[account(
+        seeds = [b"identity", owner.key().as_ref()],
+        bump,
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// The TrustToken linked from the identity record
+    #[account(
+        seeds = [b"trust_token", identity.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+}
+
+/// Context for checking a TrustToken's verification
+#[derive(Accounts)]
+pub struct CheckVerified<'info> {
+    /// The TrustToken to check
+    #[account(
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+}
+
+/// Context for burning a transferred token
+#[derive(Accounts)]
+pub struct BurnTransferredToken<'info> {
+    /// The program authority, or an attestor with the burn scope
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Attestor record, required when an attestor signs instead of the authority
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    /// Program-owned authority PDA, delegate of every TrustToken account
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The TrustToken account
+    #[account(
+        mut,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// The mint account
+    #[account(
+        mut,
+        address = trust_token.mint,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account containing the transferred token
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Metadata account for the NFT
+    /// CHECK: Validated by the Metaplex program during the thaw CPI
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Master edition account, which holds freeze authority after minting
+    /// CHECK: Validated by the Metaplex program during the thaw CPI
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// Context for reporting a transferred TrustToken
+#[derive(Accounts)]
+pub struct ReportTransferredToken<'info> {
+    /// Anyone; pays for growing the revocation log and receives the bounty
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-held vault that pays report bounties
+    #[account(
+        mut,
+        seeds = [b"fee_vault", program_state.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// The TrustToken being reported
+    #[account(
+        mut,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// The token account currently holding the TrustToken (SPL Token or Token-2022)
+    #[account(
+        token::mint = trust_token.mint,
+    )]
+    pub token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// Append-only revocation history for this TrustToken
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = RevocationLog::space(0),
+        seeds = [b"revocation_log", trust_token.mint.as_ref()],
+        bump
+    )]
+    pub revocation_log: Account<'info, RevocationLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for an owner burning their own TrustToken
+#[derive(Accounts)]
+pub struct BurnOwnToken<'info> {
+    /// The TrustToken holder (receives all reclaimed rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Program-owned authority PDA, delegate of every Metaplex TrustToken account
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The TrustToken account, closed to the owner
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ TrustTokenError::NotTokenOwner,
+        seeds = [b"trust_token", trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub trust_token: Account<'info, TrustToken>,
+
+    /// The owner's identity record, closed to the owner
+    #[account(
+        mut,
+        close = owner,
+        constraint = identity.trust_token == trust_token.key() @ TrustTokenError::NotTokenOwner,
+        seeds = [b"identity", owner.key().as_ref()],
+        bump,
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// The mint account (SPL Token or Token-2022)
+    #[account(
+        mut,
+        address = trust_token.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+
+    /// The owner's token account holding the TrustToken
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+
+    /// Metadata account, required to thaw a Metaplex TrustToken
+    /// CHECK: Validated by the Metaplex program during the thaw CPI
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    /// Master edition account, required to thaw a Metaplex TrustToken
+    /// CHECK: Validated by the Metaplex program during the thaw CPI
+    pub master_edition: Option<UncheckedAccount<'info>>,
+
+    /// Tombstone for the owner's wallet, required when the token is revoked or reported
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Tombstone::LEN,
+        seeds = [b"tombstone", owner.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Option<Account<'info, Tombstone>>,
+
+    /// The owner's recovery request, closed if one is still open
+    /// CHECK: Validated by seeds, only read and closed when it holds a RecoveryRequest
+    #[account(
+        mut,
+        seeds = [b"recovery", owner.key().as_ref()],
+        bump,
+    )]
+    pub recovery_request: UncheckedAccount<'info>,
+
+    /// The guardian that opened the recovery request, required when one is open
+    /// CHECK: Only receives the request's rent, address checked against the request
+    #[account(mut)]
+    pub recovery_initiator: Option<UncheckedAccount<'info>>,
+
+    /// The token program that owns the mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for an approved identity migration
+#[derive(Accounts)]
+pub struct MigrateIdentity<'info> {
+    /// The program authority, or an attestor with the migrate scope
+    pub approver: Signer<'info>,
+
+    /// Attestor record, required when an attestor approves instead of the authority
+    #[account(
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump,
+    )]
+    pub attestor_record: Option<Account<'info, Attestor>>,
+
+    pub migration: MigrationAccounts<'info>,
+
+    /// The old wallet's recovery request, closed if one is still open
+    /// CHECK: Validated by seeds, only read and closed when it holds a RecoveryRequest
+    #[account(
+        mut,
+        seeds = [b"recovery", migration.old_trust_token.owner.as_ref()],
+        bump,
+    )]
+    pub recovery_request: UncheckedAccount<'info>,
+
+    /// The guardian that opened the recovery request, required when one is open
+    /// CHECK: Only receives the request's rent, address checked against the request
+    #[account(mut)]
+    pub recovery_initiator: Option<UncheckedAccount<'info>>,
+}
+
+/// Context for setting guardians
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    /// The identity's owner
+    pub owner: Signer<'info>,
+
+    /// The owner's identity record
+    #[account(
+        mut,
+        seeds = [b"identity", owner.key().as_ref()],
+        bump,
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// The owner's recovery request, re-checked if one is pending
+    /// CHECK: Validated by seeds, only read and written when it holds a RecoveryRequest
+    #[account(
+        mut,
+        seeds = [b"recovery", owner.key().as_ref()],
+        bump,
+    )]
+    pub recovery_request: UncheckedAccount<'info>,
+}
+
+/// Context for opening a guardian recovery
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    /// A guardian of the identity (pays for the request)
     #[account(mut)]
-    pub minter: Signer<'info>,
+    pub guardian: Signer<'info>,
+
+    /// The identity being recovered
+    #[account(
+        seeds = [b"identity", identity.owner.as_ref()],
+        bump,
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// The recovery request
+    /// `init` fails if a recovery is already pending for this identity
+    #[account(
+        init,
+        payer = guardian,
+        space = RecoveryRequest::LEN,
+        seeds = [b"recovery", identity.owner.as_ref()],
+        bump
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for approving a guardian recovery
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    /// A guardian of the identity
+    pub guardian: Signer<'info>,
+
+    /// The identity being recovered
+    #[account(
+        seeds = [b"identity", identity.owner.as_ref()],
+        bump,
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// The pending recovery request
+    #[account(
+        mut,
+        seeds = [b"recovery", identity.owner.as_ref()],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+}
+
+/// Context for cancelling a guardian recovery
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    /// The identity's owner
+    pub owner: Signer<'info>,
+
+    /// The pending recovery request, closed to the guardian that opened it
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [b"recovery", owner.key().as_ref()],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// The guardian that opened the request
+    /// CHECK: Only receives the request's rent, address checked against the request
+    #[account(
+        mut,
+        address = recovery_request.initiated_by,
+    )]
+    pub initiator: UncheckedAccount<'info>,
+}
+
+/// Context for withdrawing a stalled guardian recovery
+#[derive(Accounts)]
+pub struct ExpireRecovery<'info> {
+    /// A guardian of the identity
+    pub guardian: Signer<'info>,
+
+    /// The identity being recovered
+    #[account(
+        seeds = [b"identity", identity.owner.as_ref()],
+        bump,
+    )]
+    pub identity: Account<'info, Identity>,
+
+    /// The pending recovery request, closed to the guardian that opened it
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [b"recovery", identity.owner.as_ref()],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// The guardian that opened the request
+    /// CHECK: Only receives the request's rent, address checked against the request
+    #[account(
+        mut,
+        address = recovery_request.initiated_by,
+    )]
+    pub initiator: UncheckedAccount<'info>,
+}
+
+/// Context for executing a guardian recovery
+#[derive(Accounts)]
+pub struct RecoverWithGuardians<'info> {
+    pub migration: MigrationAccounts<'info>,
+
+    /// The approved recovery request, closed to the guardian that opened it
+    #[account(
+        mut,
+        close = initiator,
+        constraint = recovery_request.new_owner == migration.new_owner.key() @ TrustTokenError::RecoveryNewOwnerMismatch,
+        seeds = [b"recovery", migration.old_trust_token.owner.as_ref()],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// The guardian that opened the request
+    /// CHECK: Only receives the request's rent, address checked against the request
+    #[account(
+        mut,
+        address = recovery_request.initiated_by,
+    )]
+    pub initiator: UncheckedAccount<'info>,
+}
+
+/// Accounts shared by every flow that moves a TrustToken to a new wallet
+#[derive(Accounts)]
+pub struct MigrationAccounts<'info> {
+    /// The wallet receiving the identity (pays for the replacement and receives reclaimed rent)
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    /// Program-owned authority PDA
+    /// Delegate of the old token account and authority of the replacement mint
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", program_state.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The TrustToken being migrated, closed after the state is carried over
+    #[account(
+        mut,
+        close = new_owner,
+        constraint = old_trust_token.owner != new_owner.key() @ TrustTokenError::MigrationToSameWallet,
+        seeds = [b"trust_token", old_trust_token.mint.as_ref()],
+        bump,
+    )]
+    pub old_trust_token: Box<Account<'info, TrustToken>>,
+
+    /// The old wallet's identity record, closed after the migration
+    #[account(
+        mut,
+        close = new_owner,
+        constraint = old_identity.trust_token == old_trust_token.key() @ TrustTokenError::NotTokenOwner,
+        seeds = [b"identity", old_trust_token.owner.as_ref()],
+        bump,
+    )]
+    pub old_identity: Box<Account<'info, Identity>>,
+
+    /// The old mint (SPL Token or Token-2022)
+    #[account(
+        mut,
+        address = old_trust_token.mint,
+        mint::token_program = token_program,
+    )]
+    pub old_mint: Box<InterfaceAccount<'info, InterfaceMint>>,
+
+    /// The old wallet's token account holding the TrustToken
+    #[account(
+        mut,
+        associated_token::mint = old_mint,
+        associated_token::authority = old_trust_token.owner,
+        associated_token::token_program = token_program,
+    )]
+    pub old_token_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// Metadata account of the old token, required to thaw a Metaplex TrustToken
+    /// CHECK: Validated by the Metaplex program during the thaw CPI
+    pub old_metadata: Option<UncheckedAccount<'info>>,
+
+    /// Master edition of the old token, required to thaw a Metaplex TrustToken
+    /// CHECK: Validated by the Metaplex program during the thaw CPI
+    pub old_master_edition: Option<UncheckedAccount<'info>>,
+
+    /// The replacement mint, created under the old mint's token program
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// The new wallet's associated token account for the replacement
+    /// CHECK: Created by the associated token program, which checks the address
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// TrustToken data account for the replacement
+    #[account(
+        init,
+        payer = new_owner,
+        space = TrustToken::LEN,
+        seeds = [b"trust_token", mint.key().as_ref()],
+        bump
+    )]
+    pub trust_token: Box<Account<'info, TrustToken>>,
+
+    /// Identity record for the new wallet
+    /// `init` fails if the new wallet already holds an identity
+    #[account(
+        init,
+        payer = new_owner,
+        space = Identity::LEN,
+        seeds = [b"identity", new_owner.key().as_ref()],
+        bump
+    )]
+    pub identity: Box<Account<'info, Identity>>,
+
+    /// Old → new link, keyed by the old wallet
+    /// Overwritten when the wallet is migrated away from again
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = IdentityLink::LEN,
+        seeds = [b"identity_link", old_trust_token.owner.as_ref()],
+        bump
+    )]
+    pub identity_link: Box<Account<'info, IdentityLink>>,
+
+    /// Revocation log for the replacement, chained to the old mint's log
+    #[account(
+        init,
+        payer = new_owner,
+        space = RevocationLog::space(0),
+        seeds = [b"revocation_log", mint.key().as_ref()],
+        bump
+    )]
+    pub revocation_log: Box<Account<'info, RevocationLog>>,
+
+    /// Metadata account for the replacement, for Metaplex TrustTokens
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    /// Master edition account for the replacement, for Metaplex TrustTokens
+    /// CHECK: This account is created by the Metaplex program
+    #[account(mut)]
+    pub master_edition: Option<UncheckedAccount<'info>>,
+
+    /// The TrustToken collection mint, for Metaplex TrustTokens
+    #[account(
+        seeds = [b"collection_mint", program_state.key().as_ref()],
+        bump
+    )]
+    pub collection_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Metadata account for the collection NFT, for Metaplex TrustTokens
+    /// CHECK: Validated against the collection mint by the Metaplex program
+    #[account(mut)]
+    pub collection_metadata: Option<UncheckedAccount<'info>>,
+
+    /// Master edition account for the collection NFT, for Metaplex TrustTokens
+    /// CHECK: Validated against the collection mint by the Metaplex program
+    pub collection_master_edition: Option<UncheckedAccount<'info>>,
+
+    /// The Token-2022 TrustToken group, for Token-2022 TrustTokens
+    /// CHECK: Validated by seeds; the token program checks it is an initialized group
+    #[account(
+        mut,
+        seeds = [b"collection_group", program_state.key().as_ref()],
+        bump
+    )]
+    pub collection_group: Option<UncheckedAccount<'info>>,
+
+    /// The token program of the old mint, which the replacement keeps
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Emitted once when the program is initialized
+#[event]
+pub struct ProgramInitialized {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken is minted
+#[event]
+pub struct TrustTokenMinted {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub trust_token: Pubkey,
+    pub approver: Option<Pubkey>,
+    pub level: VerificationLevel,
+    pub expires_at: Option<i64>,
+    pub total_minted: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the TrustToken collection NFT is created
+#[event]
+pub struct CollectionInitialized {
+    pub collection_mint: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the minting policy changes
+#[event]
+pub struct MintPolicyUpdated {
+    pub mint_policy: MintPolicy,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the verification period changes
+#[event]
+pub struct VerificationPeriodUpdated {
+    pub verification_period: Option<i64>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the report bounty changes
+#[event]
+pub struct ReportBountyUpdated {
+    pub report_bounty: u64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when lamports are deposited into the fee vault
+#[event]
+pub struct FeeVaultFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a new authority is proposed
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an account is grown to its current layout
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when a proposed authority accepts
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an attestor is registered
+#[event]
+pub struct AttestorRegistered {
+    pub attestor: Pubkey,
+    pub scopes: u8,
+    pub expires_at: Option<i64>,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an attestor's scopes or expiry change
+#[event]
+pub struct AttestorUpdated {
+    pub attestor: Pubkey,
+    pub scopes: u8,
+    pub expires_at: Option<i64>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an attestor is removed
+#[event]
+pub struct AttestorRemoved {
+    pub attestor: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken's verification is revoked
+#[event]
+pub struct VerificationRevoked {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub reason: RevocationReason,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken's verification is restored
+#[event]
+pub struct VerificationRestored {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub restored_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken's verification is renewed
+#[event]
+pub struct VerificationRenewed {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub expires_at: Option<i64>,
+    pub renewed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken passes the soulbound check
+#[event]
+pub struct SoulboundVerified {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub is_verified: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a transferred TrustToken is burned
+#[event]
+pub struct TransferredTokenBurned {
+    pub mint: Pubkey,
+    pub original_owner: Pubkey,
+    pub holder: Pubkey,
+    pub burned_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a transferred TrustToken is reported
+#[event]
+pub struct TransferredTokenReported {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub holder: Pubkey,
+    pub reporter: Pubkey,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a holder burns their own TrustToken
+#[event]
+pub struct TrustTokenBurned {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an identity moves to a new wallet
+#[event]
+pub struct IdentityMigrated {
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub approved_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner changes their guardians
+#[event]
+pub struct GuardiansUpdated {
+    pub owner: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a guardian opens a recovery
+#[event]
+pub struct RecoveryInitiated {
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a guardian approves a recovery
+#[event]
+pub struct RecoveryApproved {
+    pub owner: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner cancels a recovery
+#[event]
+pub struct RecoveryCancelled {
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a guardian withdraws a recovery that did not reach its threshold
+#[event]
+pub struct RecoveryExpired {
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken's verification level changes
+#[event]
+pub struct VerificationLevelChanged {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub old_level: VerificationLevel,
+    pub new_level: VerificationLevel,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a TrustToken's metadata URI is refreshed
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub uri: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Accounts needed to mint a soulbound Token-2022 TrustToken
+struct NonTransferableMint<'info> {
+    /// Receives the token and pays for the mint and its token account
+    owner: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    /// Uninitialized mint keypair, created here
+    mint: AccountInfo<'info>,
+    /// The owner's associated token account, created here
+    token_account: AccountInfo<'info>,
+    collection_group: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    associated_token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+}
+
+/// Create a NonTransferable Token-2022 TrustToken mint and mint its single token
+/// Initializes the extensions, the on-chain metadata (recording `level`) and the
+/// group membership, mints 1 token to the owner's ATA and drops the mint authority.
+fn mint_non_transferable_token(
+    accounts: NonTransferableMint,
+    name: String,
+    symbol: String,
+    uri: String,
+    level: VerificationLevel,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mint_authority_key = accounts.mint_authority.key();
+    let mint_key = accounts.mint.key();
+
+    // Size the mint for its fixed extensions; fund it for the group member and
+    // metadata TLVs as well, since the token program grows the account for those
+    let mint_len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::MetadataPointer,
+        ExtensionType::GroupMemberPointer,
+    ])?;
+    let funded_len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::MetadataPointer,
+        ExtensionType::GroupMemberPointer,
+        ExtensionType::TokenGroupMember,
+    ])?;
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(mint_authority_key))?,
+        mint: mint_key,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![(
+            VerificationLevel::METADATA_KEY.to_string(),
+            level.as_str().to_string(),
+        )],
+    };
+    let lamports = Rent::get()?.minimum_balance(funded_len + token_metadata.tlv_size_of()?);
+
+    create_account(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            CreateAccount {
+                from: accounts.owner.clone(),
+                to: accounts.mint.clone(),
+            },
+        ),
+        lamports,
+        mint_len as u64,
+        &accounts.token_program.key(),
+    )?;
+
+    // SOULBOUND: extensions must be initialized before the mint itself
+    non_transferable_mint_initialize(CpiContext::new(
+        accounts.token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: accounts.token_program.clone(),
+            mint: accounts.mint.clone(),
+        },
+    ))?;
+    // Lets the program PDA burn the token during identity migration, as the
+    // delegate approval does for Metaplex TrustTokens
+    permanent_delegate_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            PermanentDelegateInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        &mint_authority_key,
+    )?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(mint_authority_key),
+        Some(mint_key),
+    )?;
+    group_member_pointer_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            GroupMemberPointerInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(mint_authority_key),
+        Some(mint_key),
+    )?;
+    initialize_mint2(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            InitializeMint2 {
+                mint: accounts.mint.clone(),
+            },
+        ),
+        0,
+        &mint_authority_key,
+        Some(&mint_authority_key),
+    )?;
 
-    /// Program state account
-    #[account(
-        mut,
-        seeds = [b"program_state"],
-        bump
-    )]
-    pub program_state: Account<'info, ProgramState>,
+    // Store name/symbol/uri on the mint, with the program PDA as update authority
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: accounts.token_program.clone(),
+                metadata: accounts.mint.clone(),
+                update_authority: accounts.mint_authority.clone(),
+                mint_authority: accounts.mint_authority.clone(),
+                mint: accounts.mint.clone(),
+            },
+            signer_seeds,
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TokenMetadataUpdateField {
+                program_id: accounts.token_program.clone(),
+                metadata: accounts.mint.clone(),
+                update_authority: accounts.mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        Field::Key(VerificationLevel::METADATA_KEY.to_string()),
+        level.as_str().to_string(),
+    )?;
 
-    /// The mint account for the NFT
-    /// Each TrustToken gets a unique mint
-    #[account(
-        init,
-        payer = minter,
-        mint::decimals = 0,
-        mint::authority = minter,
-        mint::freeze_authority = minter,
-    )]
-    pub mint: Account<'info, Mint>,
+    // Join the TrustToken group, so membership proves the token is authentic
+    token_member_initialize(CpiContext::new_with_signer(
+        accounts.token_program.clone(),
+        TokenMemberInitialize {
+            program_id: accounts.token_program.clone(),
+            member: accounts.mint.clone(),
+            member_mint: accounts.mint.clone(),
+            member_mint_authority: accounts.mint_authority.clone(),
+            group: accounts.collection_group.clone(),
+            group_update_authority: accounts.mint_authority.clone(),
+        },
+        signer_seeds,
+    ))?;
 
-    /// The token account that will hold the NFT
-    /// Uses associated token account for standardization
-    #[account(
-        init_if_needed,
-        payer = minter,
-        associated_token::mint = mint,
-        associated_token::authority = minter,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
+    // Create the minter's associated token account (ImmutableOwner is implied)
+    create_associated_token_account(CpiContext::new(
+        accounts.associated_token_program.clone(),
+        CreateAssociatedTokenAccount {
+            payer: accounts.owner.clone(),
+            associated_token: accounts.token_account.clone(),
+            authority: accounts.owner.clone(),
+            mint: accounts.mint.clone(),
+            system_program: accounts.system_program.clone(),
+            token_program: accounts.token_program.clone(),
+        },
+    ))?;
 
-    /// TrustToken data account
-    /// Stores verification status and metadata
-    #[account(
-        init,
-        payer = minter,
-        space = TrustToken::LEN,
-        seeds = [b"trust_token", mint.key().as_ref()],
-        bump
-    )]
-    pub trust_token: Account<'info, TrustToken>,
+    // Mint 1 token, then drop the mint authority so no more can ever exist
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_2022::MintTo {
+                mint: accounts.mint.clone(),
+                to: accounts.token_account.clone(),
+                authority: accounts.mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_2022::SetAuthority {
+                current_authority: accounts.mint_authority.clone(),
+                account_or_mint: accounts.mint.clone(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    Ok(())
+}
 
-    /// Metadata account for the NFT
-    /// CHECK: This account is created by the Metaplex program
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
+/// Accounts needed to mint a soulbound Metaplex TrustToken NFT
+struct MetaplexMint<'info> {
+    /// Receives the token, pays for the Metaplex accounts and signs the delegate approval
+    owner: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    collection_mint: AccountInfo<'info>,
+    collection_metadata: AccountInfo<'info>,
+    collection_master_edition: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    token_metadata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: AccountInfo<'info>,
+}
 
-    /// Master edition account for the NFT
-    /// CHECK: This account is created by the Metaplex program
-    #[account(mut)]
-    pub master_edition: UncheckedAccount<'info>,
+/// Mint a TrustToken NFT into an initialized mint and token account
+/// Mints 1 token, approves the program PDA as delegate, freezes the account,
+/// creates the metadata and master edition and verifies it into the collection.
+fn mint_metaplex_token(
+    accounts: MetaplexMint,
+    name: String,
+    symbol: String,
+    uri: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Mint 1 token to the owner's associated token account
+    let cpi_context = CpiContext::new_with_signer(
+        accounts.token_program.clone(),
+        MintTo {
+            mint: accounts.mint.clone(),
+            to: accounts.token_account.clone(),
+            authority: accounts.mint_authority.clone(),
+        },
+        signer_seeds,
+    );
+    mint_to(cpi_context, 1)?;
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_metadata_program: Program<'info, Metadata>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    // Approve the mint authority PDA as delegate while the account is still thawed.
+    // Once the master edition takes over freeze authority, the program can only
+    // thaw (and then burn) the token through Metaplex as the account's delegate.
+    let approve_cpi_context = CpiContext::new(
+        accounts.token_program.clone(),
+        Approve {
+            to: accounts.token_account.clone(),
+            delegate: accounts.mint_authority.clone(),
+            authority: accounts.owner.clone(),
+        },
+    );
+    approve(approve_cpi_context, 1)?;
+
+    // SOULBOUND: Freeze the token account immediately after minting
+    // This prevents any transfers, making the NFT permanently bound to this wallet
+    let freeze_cpi_context = CpiContext::new_with_signer(
+        accounts.token_program.clone(),
+        FreezeAccount {
+            account: accounts.token_account.clone(),
+            mint: accounts.mint.clone(),
+            authority: accounts.mint_authority.clone(),
+        },
+        signer_seeds,
+    );
+    freeze_account(freeze_cpi_context)?;
+
+    // Create metadata account for the NFT
+    let creator = vec![Creator {
+        address: accounts.owner.key(),
+        verified: true,
+        share: 100,
+    }];
+
+    let metadata_data = DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0, // No royalties for identity tokens
+        creators: Some(creator),
+        // Attached unverified here, verified below once the master edition exists
+        collection: Some(Collection {
+            verified: false,
+            key: accounts.collection_mint.key(),
+        }),
+        uses: None,
+    };
+
+    let cpi_context = CpiContext::new_with_signer(
+        accounts.token_metadata_program.clone(),
+        CreateMetadataAccountsV3 {
+            metadata: accounts.metadata.clone(),
+            mint: accounts.mint.clone(),
+            mint_authority: accounts.mint_authority.clone(),
+            // The program PDA, not the holder, controls the token's name and URI
+            update_authority: accounts.mint_authority.clone(),
+            payer: accounts.owner.clone(),
+            system_program: accounts.system_program.clone(),
+            rent: accounts.rent.clone(),
+        },
+        signer_seeds,
+    );
+    create_metadata_accounts_v3(cpi_context, metadata_data, true, true, None)?;
+
+    // Create master edition to make this a true NFT (supply = 1, no more can be minted)
+    let cpi_context = CpiContext::new_with_signer(
+        accounts.token_metadata_program.clone(),
+        CreateMasterEditionV3 {
+            edition: accounts.master_edition.clone(),
+            mint: accounts.mint.clone(),
+            update_authority: accounts.mint_authority.clone(),
+            mint_authority: accounts.mint_authority.clone(),
+            payer: accounts.owner.clone(),
+            metadata: accounts.metadata.clone(),
+            token_program: accounts.token_program.clone(),
+            system_program: accounts.system_program.clone(),
+            rent: accounts.rent.clone(),
+        },
+        signer_seeds,
+    );
+    create_master_edition_v3(cpi_context, Some(0))?; // max_supply = 0 means only 1 can exist
+
+    // Verify the token into the TrustToken collection; the PDA is the collection's update authority
+    let cpi_context = CpiContext::new_with_signer(
+        accounts.token_metadata_program.clone(),
+        VerifySizedCollectionItem {
+            payer: accounts.owner.clone(),
+            metadata: accounts.metadata.clone(),
+            collection_authority: accounts.mint_authority.clone(),
+            collection_mint: accounts.collection_mint.clone(),
+            collection_metadata: accounts.collection_metadata.clone(),
+            collection_master_edition: accounts.collection_master_edition.clone(),
+        },
+        signer_seeds,
+    );
+    verify_sized_collection_item(cpi_context, None)?;
+    Ok(())
 }
 
-/// Context for revoking verification
-#[derive(Accounts)]
-pub struct RevokeVerification<'info> {
-    /// The authority that can revoke verification
-    pub authority: Signer<'info>,
+/// Move a TrustToken to `new_owner`: burn the old token, mint a replacement,
+/// carry the verification state over and record the old → new link
+fn migrate_trust_token(
+    accounts: &mut MigrationAccounts,
+    bumps: &MigrationAccountsBumps,
+    approved_by: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    // Validate input lengths to prevent excessive storage costs
+    require!(name.len() <= 32, TrustTokenError::NameTooLong);
+    require!(symbol.len() <= 10, TrustTokenError::SymbolTooLong);
+    require!(uri.len() <= 200, TrustTokenError::UriTooLong);
 
-    /// Program state account
-    #[account(
-        seeds = [b"program_state"],
-        bump
-    )]
-    pub program_state: Account<'info, ProgramState>,
+    let program_state_key = accounts.program_state.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"mint_authority",
+        program_state_key.as_ref(),
+        &[bumps.mint_authority],
+    ]];
 
-    /// The TrustToken account to revoke
-    #[account(
-        mut,
-        seeds = [b"trust_token", trust_token.mint.as_ref()],
-        bump,
-    )]
-    pub trust_token: Account<'info, TrustToken>,
+    // Frozen Metaplex tokens are thawed through the master edition first
+    if accounts.old_token_account.is_frozen() {
+        let (metadata, master_edition, token_metadata_program) = match (
+            accounts.old_metadata.as_ref(),
+            accounts.old_master_edition.as_ref(),
+            accounts.token_metadata_program.as_ref(),
+        ) {
+            (Some(metadata), Some(master_edition), Some(program)) => {
+                (metadata, master_edition, program)
+            }
+            _ => return err!(TrustTokenError::MissingMetadataAccounts),
+        };
+
+        let thaw_cpi_context = CpiContext::new_with_signer(
+            token_metadata_program.to_account_info(),
+            ThawDelegatedAccount {
+                metadata: metadata.to_account_info(),
+                delegate: accounts.mint_authority.to_account_info(),
+                token_account: accounts.old_token_account.to_account_info(),
+                edition: master_edition.to_account_info(),
+                mint: accounts.old_mint.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        thaw_delegated_account(thaw_cpi_context)?;
+    }
+
+    // Burn the old token as its delegate (Metaplex) or permanent delegate (Token-2022),
+    // unless the holder already burned it through Token-2022 directly
+    if accounts.old_token_account.amount > 0 {
+        let cpi_context = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            token_interface::Burn {
+                mint: accounts.old_mint.to_account_info(),
+                from: accounts.old_token_account.to_account_info(),
+                authority: accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::burn(cpi_context, 1)?;
+    }
+
+    // Mint the replacement to the new wallet, keeping the old token's standard
+    if accounts.token_program.key() == token_2022::ID {
+        let collection_group = accounts
+            .collection_group
+            .as_ref()
+            .ok_or(TrustTokenError::MissingMetadataAccounts)?;
+        mint_non_transferable_token(
+            NonTransferableMint {
+                owner: accounts.new_owner.to_account_info(),
+                mint_authority: accounts.mint_authority.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                token_account: accounts.token_account.to_account_info(),
+                collection_group: collection_group.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                associated_token_program: accounts.associated_token_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+            },
+            name,
+            symbol,
+            uri,
+            accounts.old_trust_token.level,
+            signer_seeds,
+        )?;
+    } else {
+        let (
+            metadata,
+            master_edition,
+            collection_mint,
+            collection_metadata,
+            collection_master_edition,
+            token_metadata_program,
+        ) = match (
+            accounts.metadata.as_ref(),
+            accounts.master_edition.as_ref(),
+            accounts.collection_mint.as_ref(),
+            accounts.collection_metadata.as_ref(),
+            accounts.collection_master_edition.as_ref(),
+            accounts.token_metadata_program.as_ref(),
+        ) {
+            (Some(a), Some(b), Some(c), Some(d), Some(e), Some(f)) => (a, b, c, d, e, f),
+            _ => return err!(TrustTokenError::MissingMetadataAccounts),
+        };
+
+        // `mint` creates these through account constraints; here the token program is only
+        // known once the old mint is loaded, so the mint and token account are made by hand
+        let mint_authority_key = accounts.mint_authority.key();
+        create_account(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: accounts.new_owner.to_account_info(),
+                    to: accounts.mint.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &accounts.token_program.key(),
+        )?;
+        initialize_mint2(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: accounts.mint.to_account_info(),
+                },
+            ),
+            0,
+            &mint_authority_key,
+            Some(&mint_authority_key),
+        )?;
+        create_associated_token_account(CpiContext::new(
+            accounts.associated_token_program.to_account_info(),
+            CreateAssociatedTokenAccount {
+                payer: accounts.new_owner.to_account_info(),
+                associated_token: accounts.token_account.to_account_info(),
+                authority: accounts.new_owner.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+            },
+        ))?;
+
+        mint_metaplex_token(
+            MetaplexMint {
+                owner: accounts.new_owner.to_account_info(),
+                mint_authority: accounts.mint_authority.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                token_account: accounts.token_account.to_account_info(),
+                metadata: metadata.to_account_info(),
+                master_edition: master_edition.to_account_info(),
+                collection_mint: collection_mint.to_account_info(),
+                collection_metadata: collection_metadata.to_account_info(),
+                collection_master_edition: collection_master_edition.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                token_metadata_program: token_metadata_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                rent: accounts.rent.to_account_info(),
+            },
+            name,
+            symbol,
+            uri,
+            signer_seeds,
+        )?;
+    }
+
+    // Carry the verification state over to the replacement
+    let old_trust_token = &accounts.old_trust_token;
+    let trust_token = &mut accounts.trust_token;
+    trust_token.owner = accounts.new_owner.key();
+    trust_token.mint = accounts.mint.key();
+    trust_token.is_verified = old_trust_token.is_verified;
+    trust_token.minted_at = old_trust_token.minted_at;
+    trust_token.expires_at = old_trust_token.expires_at;
+    trust_token.level = old_trust_token.level;
+    trust_token.revoked_at = old_trust_token.revoked_at;
+    trust_token.revoked_by = old_trust_token.revoked_by;
+    trust_token.compromised_at = None;
+
+    let identity = &mut accounts.identity;
+    identity.owner = trust_token.owner;
+    identity.mint = trust_token.mint;
+    identity.trust_token = trust_token.key();
+    identity.created_at = accounts.old_identity.created_at;
+    identity.migrated_from = Some(old_trust_token.owner);
+    identity.guardians = accounts.old_identity.guardians.clone();
+    identity.guardian_threshold = accounts.old_identity.guardian_threshold;
+
+    let now = Clock::get()?.unix_timestamp;
+    let identity_link = &mut accounts.identity_link;
+    identity_link.old_owner = old_trust_token.owner;
+    identity_link.new_owner = trust_token.owner;
+    identity_link.old_mint = old_trust_token.mint;
+    identity_link.new_mint = trust_token.mint;
+    identity_link.approved_by = approved_by;
+    identity_link.migrated_at = now;
+
+    // Revocation history stays keyed by mint; chaining the logs keeps it reachable
+    let revocation_log = &mut accounts.revocation_log;
+    revocation_log.mint = trust_token.mint;
+    revocation_log.previous_mint = Some(old_trust_token.mint);
+
+    emit!(IdentityMigrated {
+        old_owner: identity_link.old_owner,
+        new_owner: identity_link.new_owner,
+        old_mint: identity_link.old_mint,
+        new_mint: identity_link.new_mint,
+        approved_by,
+        timestamp: now,
+    });
+
+    msg!(
+        "Identity migrated: {} → {} (TrustToken {} → {})",
+        identity_link.old_owner,
+        identity_link.new_owner,
+        identity_link.old_mint,
+        identity_link.new_mint
+    );
+    Ok(())
 }
 
-/// Context for restoring verification
-#[derive(Accounts)]
-pub struct RestoreVerification<'info> {
-    /// The authority that can restore verification
-    pub authority: Signer<'info>,
+/// Count a recovery's approvals from wallets that are still guardians of the identity
+fn count_guardian_approvals(identity: &Identity, recovery_request: &RecoveryRequest) -> u8 {
+    recovery_request
+        .approvals
+        .iter()
+        .filter(|approval| identity.guardians.contains(approval))
+        .count() as u8
+}
 
-    /// Program state account
-    #[account(
-        seeds = [b"program_state"],
-        bump
-    )]
-    pub program_state: Account<'info, ProgramState>,
+/// Close the recovery request of a wallet whose identity is going away, if one is open
+/// Refunds the rent to the guardian that opened it, who must be passed as `initiator`.
+fn close_recovery_request<'info>(
+    recovery_request: &UncheckedAccount<'info>,
+    initiator: Option<&UncheckedAccount<'info>>,
+) -> Result<()> {
+    let recovery_info = recovery_request.to_account_info();
+    if recovery_info.owner != &crate::ID || recovery_info.data_is_empty() {
+        return Ok(());
+    }
 
-    /// The TrustToken account to restore
-    #[account(
-        mut,
-        seeds = [b"trust_token", trust_token.mint.as_ref()],
-        bump,
-    )]
-    pub trust_token: Account<'info, TrustToken>,
+    let recovery_request =
+        RecoveryRequest::try_deserialize(&mut &recovery_info.try_borrow_data()?[..])?;
+    let initiator = initiator
+        .filter(|initiator| initiator.key() == recovery_request.initiated_by)
+        .ok_or(TrustTokenError::RecoveryInitiatorMismatch)?;
+
+    emit!(RecoveryCancelled {
+        owner: recovery_request.owner,
+        new_owner: recovery_request.new_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    let initiator_info = initiator.to_account_info();
+    let refund = initiator_info
+        .lamports()
+        .checked_add(recovery_info.lamports())
+        .ok_or(TrustTokenError::Overflow)?;
+    **initiator_info.try_borrow_mut_lamports()? = refund;
+    **recovery_info.try_borrow_mut_lamports()? = 0;
+    recovery_info.assign(&system_program::ID);
+    recovery_info.resize(0)?;
+    Ok(())
 }
 
-/// Context for verifying soulbound status
-#[derive(Accounts)]
-pub struct VerifySoulbound<'info> {
-    /// The TrustToken account to verify
-    #[account(
-        seeds = [b"trust_token", trust_token.mint.as_ref()],
-        bump,
-    )]
-    pub trust_token: Account<'info, TrustToken>,
+/// Check that a mint satisfies `ProgramState::mint_policy`, tightened for tombstoned wallets
+fn check_mint_policy(
+    program_state: &ProgramState,
+    approver: Option<&Signer>,
+    attestor_record: Option<&Account<Attestor>>,
+    tombstoned: bool,
+) -> Result<()> {
+    match program_state.mint_policy.for_wallet(tombstoned) {
+        MintPolicy::Open => {}
+        MintPolicy::AuthorityOnly => {
+            let approver = approver.ok_or(TrustTokenError::UnauthorizedMint)?;
+            require!(
+                approver.key() == program_state.authority,
+                TrustTokenError::UnauthorizedMint
+            );
+        }
+        MintPolicy::AttestorSigned => {
+            let approver = approver.ok_or(TrustTokenError::UnauthorizedMint)?;
+            require!(
+                is_authorized(
+                    program_state,
+                    attestor_record,
+                    approver.key(),
+                    Attestor::SCOPE_MINT_APPROVE,
+                )?,
+                TrustTokenError::UnauthorizedMint
+            );
+        }
+    }
+    Ok(())
+}
 
-    /// The mint account
-    pub mint: Account<'info, Mint>,
+/// Fill in the TrustToken and identity records for a freshly minted token
+/// and bump the program's mint counter
+fn record_mint(
+    program_state: &mut ProgramState,
+    trust_token: &mut Account<TrustToken>,
+    identity: &mut Account<Identity>,
+    owner: Pubkey,
+    mint: Pubkey,
+    approver: Option<Pubkey>,
+) -> Result<()> {
+    // Initialize the TrustToken account data
+    trust_token.owner = owner;
+    trust_token.mint = mint;
+    trust_token.is_verified = true; // Minting policy already gated verification
+    trust_token.minted_at = Clock::get()?.unix_timestamp;
+    trust_token.expires_at = program_state.verification_expiry(trust_token.minted_at)?;
+    trust_token.level = VerificationLevel::Basic;
+    trust_token.revoked_at = None;
+    trust_token.revoked_by = None;
+    trust_token.compromised_at = None;
 
-    /// The token account to verify
-    #[account(
-        associated_token::mint = mint,
-        associated_token::authority = trust_token.owner,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
+    // Link the wallet's identity record to this TrustToken
+    identity.owner = owner;
+    identity.mint = mint;
+    identity.trust_token = trust_token.key();
+    identity.created_at = trust_token.minted_at;
+    identity.migrated_from = None;
+    identity.guardians = Vec::new();
+    identity.guardian_threshold = 0;
+
+    // Update program state
+    program_state.total_minted = program_state
+        .total_minted
+        .checked_add(1)
+        .ok_or(TrustTokenError::Overflow)?;
+
+    emit!(TrustTokenMinted {
+        owner,
+        mint,
+        trust_token: trust_token.key(),
+        approver,
+        level: trust_token.level,
+        expires_at: trust_token.expires_at,
+        total_minted: program_state.total_minted,
+        timestamp: trust_token.minted_at,
+    });
+    Ok(())
 }
 
-/// Context for burning a transferred token
-#[derive(Accounts)]
-pub struct BurnTransferredToken<'info> {
-    /// The authority that can burn tokens
-    #[account(mut)]
-    pub authority: Signer<'info>,
+/// Check whether `signer` may perform an action gated by `scope`
+/// The program authority may always act; anyone else must present an active
+/// attestor record for their own key that grants the scope.
+fn is_authorized(
+    program_state: &ProgramState,
+    attestor_record: Option<&Account<Attestor>>,
+    signer: Pubkey,
+    scope: u8,
+) -> Result<bool> {
+    if signer == program_state.authority {
+        return Ok(true);
+    }
 
-    /// Program state account
-    #[account(
-        seeds = [b"program_state"],
-        bump
-    )]
-    pub program_state: Account<'info, ProgramState>,
+    let now = Clock::get()?.unix_timestamp;
+    Ok(attestor_record
+        .is_some_and(|record| record.attestor == signer && record.allows(scope, now)))
+}
 
-    /// The TrustToken account
-    #[account(
-        mut,
-        seeds = [b"trust_token", trust_token.mint.as_ref()],
-        bump,
-    )]
-    pub trust_token: Account<'info, TrustToken>,
+/// Require that `trust_token` belongs to `owner` and is verified and unexpired
+/// Meant for other programs: depend on this crate with the `cpi` feature, load the
+/// holder's `Account<TrustToken>` (checking its `[b"trust_token", mint]` seeds
+/// against this program) and call this before acting on their behalf.
+pub fn require_verified(trust_token: &TrustToken, owner: Pubkey, clock: &Clock) -> Result<()> {
+    require_keys_eq!(trust_token.owner, owner, TrustTokenError::NotTokenOwner);
+    require!(trust_token.is_verified, TrustTokenError::NotVerified);
+    require!(
+        !trust_token.is_expired(clock.unix_timestamp),
+        TrustTokenError::VerificationExpired
+    );
+    Ok(())
+}
 
-    /// The mint account
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+/// Grow a program-owned account to `new_len`, with `payer` covering the extra rent
+/// The new bytes are zeroed, which decodes as the default (0, false, None, first
+/// variant, empty Vec) for fields appended after the account was created.
+/// Shared with the other programs' `migrate_*` instructions; callers check
+/// the account is still on an older layout first.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let old_len = account.data_len();
+    let rent = Rent::get()?;
+    let top_up = rent
+        .minimum_balance(new_len)
+        .saturating_sub(rent.minimum_balance(old_len));
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
 
-    /// The token account containing the transferred token
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
+/// Accounts needed to rewrite a TrustToken's on-chain metadata
+struct TokenMetadataWrite<'info> {
+    /// Pays for any extra space the Token-2022 metadata needs
+    payer: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    /// Metaplex metadata, for TrustTokens minted through `mint`
+    metadata: Option<Account<'info, MetadataAccount>>,
+    token_metadata_program: Option<AccountInfo<'info>>,
+    token_2022_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+}
 
-    pub token_program: Program<'info, Token>,
+/// Write the verification level and, optionally, a new URI into a TrustToken's metadata
+/// Token-2022 TrustTokens keep the level under `VerificationLevel::METADATA_KEY` in their
+/// additional metadata; the mint is topped up first if the new values need more space.
+/// Metaplex metadata has no such field, so there the level lives in the off-chain JSON
+/// and only the URI is rewritten (name, symbol, creators and collection are kept).
+fn write_token_metadata(
+    accounts: TokenMetadataWrite,
+    level: VerificationLevel,
+    uri: Option<String>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if *accounts.mint.owner == token_2022::ID {
+        let current = {
+            let data = accounts.mint.try_borrow_data()?;
+            StateWithExtensions::<Token2022Mint>::unpack(&data)?
+                .get_variable_len_extension::<TokenMetadata>()?
+        };
+        let mut updated = current.clone();
+        updated.update(
+            Field::Key(VerificationLevel::METADATA_KEY.to_string()),
+            level.as_str().to_string(),
+        );
+        if let Some(ref uri) = uri {
+            updated.update(Field::Uri, uri.clone());
+        }
+
+        // The token program reallocates the mint itself but cannot fund it
+        let new_len = (accounts.mint.data_len() + updated.tlv_size_of()?)
+            .saturating_sub(current.tlv_size_of()?);
+        let required = Rent::get()?.minimum_balance(new_len);
+        let lamports = accounts.mint.lamports();
+        if required > lamports {
+            transfer(
+                CpiContext::new(
+                    accounts.system_program.clone(),
+                    Transfer {
+                        from: accounts.payer.clone(),
+                        to: accounts.mint.clone(),
+                    },
+                ),
+                required - lamports,
+            )?;
+        }
+
+        let mut fields = vec![(
+            Field::Key(VerificationLevel::METADATA_KEY.to_string()),
+            level.as_str().to_string(),
+        )];
+        if let Some(uri) = uri {
+            fields.push((Field::Uri, uri));
+        }
+        for (field, value) in fields {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    accounts.token_2022_program.clone(),
+                    TokenMetadataUpdateField {
+                        program_id: accounts.token_2022_program.clone(),
+                        metadata: accounts.mint.clone(),
+                        update_authority: accounts.mint_authority.clone(),
+                    },
+                    signer_seeds,
+                ),
+                field,
+                value,
+            )?;
+        }
+    } else if let Some(uri) = uri {
+        let metadata = accounts
+            .metadata
+            .ok_or(TrustTokenError::MissingMetadataAccounts)?;
+        let token_metadata_program = accounts
+            .token_metadata_program
+            .ok_or(TrustTokenError::MissingMetadataAccounts)?;
+
+        // Metaplex stores strings null-padded; strip the padding before writing them back
+        let metadata_data = DataV2 {
+            name: metadata.name.trim_end_matches('\0').to_string(),
+            symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+            uri,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        };
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                token_metadata_program,
+                UpdateMetadataAccountsV2 {
+                    metadata: metadata.to_account_info(),
+                    update_authority: accounts.mint_authority,
+                },
+                signer_seeds,
+            ),
+            None,
+            Some(metadata_data),
+            None,
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+/// Append `entry` to a revocation log, growing the account and topping up rent from `payer`
+fn append_revocation_entry<'info>(
+    revocation_log: &mut Account<'info, RevocationLog>,
+    mint: Pubkey,
+    entry: RevocationEntry,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let new_len = RevocationLog::space(revocation_log.entries.len() + 1);
+    let log_info = revocation_log.to_account_info();
+
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(log_info.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: log_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    log_info.resize(new_len)?;
+
+    // A freshly created log has no mint recorded yet
+    revocation_log.mint = mint;
+    revocation_log.entries.push(entry);
+    Ok(())
 }
 
 // ============================================================================
@@ -511,16 +5003,16 @@ pub struct BurnTransferredToken<'info> {
 /// Custom error codes for the TrustToken program
 #[error_code]
 pub enum TrustTokenError {
-    #[msg("Only the program authority can mint TrustTokens")]
+    #[msg("Minting requires approval from the program authority or a registered attestor")]
     UnauthorizedMint,
 
-    #[msg("Only the program authority can revoke verification")]
+    #[msg("Only the program authority or an attestor with the revoke scope can revoke verification")]
     UnauthorizedRevoke,
 
-    #[msg("Only the program authority can restore verification")]
+    #[msg("Only the program authority or an attestor with the restore scope can restore verification")]
     UnauthorizedRestore,
 
-    #[msg("Only the program authority can burn tokens")]
+    #[msg("Only the program authority or an attestor with the burn scope can burn tokens")]
     UnauthorizedBurn,
 
     #[msg("Name must be 32 characters or less")]
@@ -546,4 +5038,228 @@ pub enum TrustTokenError {
 
     #[msg("Token has not been transferred - cannot burn")]
     TokenNotTransferred,
+
+    #[msg("Only the program authority can change program configuration")]
+    UnauthorizedConfig,
+
+    #[msg("Attestor scopes contain unknown bits")]
+    InvalidAttestorScopes,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Signer does not own this TrustToken")]
+    NotTokenOwner,
+
+    #[msg("Only the program authority or an attestor with the renew scope can renew verification")]
+    UnauthorizedRenew,
+
+    #[msg("Verification period must be positive")]
+    InvalidVerificationPeriod,
+
+    #[msg("TrustToken verification has expired")]
+    VerificationExpired,
+
+    #[msg("Only the program authority can change verification levels")]
+    UnauthorizedLevelChange,
+
+    #[msg("Memo must be 200 characters or less")]
+    MemoTooLong,
+
+    #[msg("TrustToken verification is already revoked")]
+    AlreadyRevoked,
+
+    #[msg("TrustToken verification is not revoked")]
+    NotRevoked,
+
+    #[msg("Metadata account does not belong to this TrustToken")]
+    MetadataMismatch,
+
+    #[msg("Metaplex TrustTokens need a new metadata URI to record the level")]
+    MissingMetadataUri,
+
+    #[msg("Metaplex metadata accounts are required for this TrustToken")]
+    MissingMetadataAccounts,
+
+    #[msg("Burning a revoked or reported TrustToken requires its tombstone account")]
+    MissingTombstone,
+
+    #[msg("Only the program authority or an attestor with the migrate scope can migrate identities")]
+    UnauthorizedMigration,
+
+    #[msg("Identity cannot be migrated to the wallet that already holds it")]
+    MigrationToSameWallet,
+
+    #[msg("Too many guardians")]
+    TooManyGuardians,
+
+    #[msg("Guardian threshold must be between 1 and the number of guardians (0 with no guardians)")]
+    InvalidGuardianThreshold,
+
+    #[msg("Guardians must be distinct and cannot include the owner")]
+    InvalidGuardian,
+
+    #[msg("Signer is not a guardian of this identity")]
+    NotGuardian,
+
+    #[msg("Guardian has already approved this recovery")]
+    AlreadyApproved,
+
+    #[msg("Recovery does not have enough guardian approvals")]
+    RecoveryNotApproved,
+
+    #[msg("Recovery delay has not elapsed")]
+    RecoveryDelayNotElapsed,
+
+    #[msg("Signer is not the new owner named in the recovery request")]
+    RecoveryNewOwnerMismatch,
+
+    #[msg("Recovery initiator does not match the open recovery request")]
+    RecoveryInitiatorMismatch,
+
+    #[msg("Recovery has reached its approval threshold")]
+    RecoveryAlreadyApproved,
+
+    #[msg("Recovery request has not timed out yet")]
+    RecoveryNotExpired,
+
+    #[msg("TrustToken has already been reported as transferred")]
+    AlreadyReported,
+
+    #[msg("TrustToken is not verified")]
+    NotVerified,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_state(report_bounty: u64) -> ProgramState {
+        ProgramState {
+            authority: Pubkey::new_unique(),
+            total_minted: 0,
+            mint_policy: MintPolicy::Open,
+            pending_authority: None,
+            verification_period: None,
+            total_burned: 0,
+            report_bounty,
+        }
+    }
+
+    #[test]
+    fn report_pays_the_full_bounty_from_a_funded_vault() {
+        let program_state = program_state(1_000_000);
+        assert_eq!(program_state.report_payout(5_000_000, 890_880), 1_000_000);
+        assert_eq!(program_state.report_payout(1_890_880, 890_880), 1_000_000);
+    }
+
+    #[test]
+    fn report_never_takes_the_vault_below_rent() {
+        let program_state = program_state(1_000_000);
+        assert_eq!(program_state.report_payout(1_200_000, 890_880), 309_120);
+        assert_eq!(program_state.report_payout(890_880, 890_880), 0);
+        assert_eq!(program_state.report_payout(500_000, 890_880), 0);
+        assert_eq!(program_state.report_payout(0, 890_880), 0);
+    }
+
+    #[test]
+    fn tombstoned_wallets_cannot_self_mint() {
+        assert_eq!(MintPolicy::Open.for_wallet(false), MintPolicy::Open);
+        assert_eq!(MintPolicy::Open.for_wallet(true), MintPolicy::AttestorSigned);
+        assert_eq!(MintPolicy::AuthorityOnly.for_wallet(true), MintPolicy::AuthorityOnly);
+        assert_eq!(MintPolicy::AttestorSigned.for_wallet(true), MintPolicy::AttestorSigned);
+    }
+
+    fn guarded_identity(guardians: &[Pubkey], threshold: u8) -> Identity {
+        Identity {
+            owner: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            trust_token: Pubkey::new_unique(),
+            created_at: 0,
+            migrated_from: None,
+            guardians: guardians.to_vec(),
+            guardian_threshold: threshold,
+        }
+    }
+
+    fn recovery_request(identity: &Identity, approvals: &[Pubkey], now: i64) -> RecoveryRequest {
+        let mut request = RecoveryRequest {
+            owner: identity.owner,
+            new_owner: Pubkey::new_unique(),
+            initiated_by: approvals[0],
+            approvals: approvals.to_vec(),
+            initiated_at: now,
+            approved_at: None,
+        };
+        request.refresh_approval(identity, now);
+        request
+    }
+
+    #[test]
+    fn recovery_delay_starts_when_the_threshold_is_reached() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let identity = guarded_identity(&guardians, 2);
+
+        let mut request = recovery_request(&identity, &guardians[..1], 100);
+        assert!(!request.is_approved(&identity));
+        assert_eq!(request.approved_at, None);
+
+        request.approvals.push(guardians[1]);
+        request.refresh_approval(&identity, 500);
+        assert!(request.is_approved(&identity));
+        assert_eq!(request.approved_at, Some(500));
+
+        // Further approvals do not restart the delay
+        request.approvals.push(guardians[2]);
+        request.refresh_approval(&identity, 900);
+        assert_eq!(request.approved_at, Some(500));
+
+        assert!(!request.is_ready(&identity, 500 + RecoveryRequest::DELAY - 1).unwrap());
+        assert!(request.is_ready(&identity, 500 + RecoveryRequest::DELAY).unwrap());
+    }
+
+    #[test]
+    fn single_guardian_recovery_is_approved_on_initiation() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let identity = guarded_identity(&guardians, 1);
+
+        let request = recovery_request(&identity, &guardians[1..], 100);
+        assert_eq!(request.approved_at, Some(100));
+        assert!(!request.is_ready(&identity, 100).unwrap());
+    }
+
+    #[test]
+    fn guardian_changes_reset_the_recovery_delay() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut identity = guarded_identity(&guardians, 2);
+        let mut request = recovery_request(&identity, &guardians[..2], 100);
+        assert_eq!(request.approved_at, Some(100));
+
+        // Removing an approving guardian drops the request below the threshold
+        identity.guardians = vec![guardians[0], guardians[2]];
+        request.refresh_approval(&identity, 200);
+        assert_eq!(request.approved_at, None);
+        assert!(!request.is_ready(&identity, 200 + RecoveryRequest::DELAY).unwrap());
+
+        // Lowering the threshold reaches it again, and the delay starts afresh
+        identity.guardian_threshold = 1;
+        request.refresh_approval(&identity, 300);
+        assert_eq!(request.approved_at, Some(300));
+        assert!(!request.is_ready(&identity, 100 + RecoveryRequest::DELAY).unwrap());
+        assert!(request.is_ready(&identity, 300 + RecoveryRequest::DELAY).unwrap());
+    }
+
+    #[test]
+    fn stale_approval_time_is_not_ready() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let identity = guarded_identity(&guardians, 2);
+        let mut request = recovery_request(&identity, &guardians[..1], 100);
+
+        // An approval time without the approvals to back it never makes the request ready
+        request.approved_at = Some(100);
+        assert!(!request.is_ready(&identity, 100 + RecoveryRequest::DELAY).unwrap());
+    }
 }
@@ -0,0 +1,208 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TrustToken } from "../target/types/trust_token";
+import { ReputationCard } from "../target/types/reputation_card";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("reputation_card", () => {
+  // Configure the client to use the local cluster
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const trustToken = new Program<TrustToken>(
+    require("../target/idl/trust_token.json"),
+    provider
+  );
+  const program = new Program<ReputationCard>(
+    require("../target/idl/reputation_card.json"),
+    provider
+  );
+  const authority = provider.wallet as anchor.Wallet;
+  const marketplace = Keypair.generate();
+
+  const [programStatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_state")],
+    program.programId
+  );
+  const [trustTokenStatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_state")],
+    trustToken.programId
+  );
+  const [collectionGroupPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("collection_group"), trustTokenStatePda.toBuffer()],
+    trustToken.programId
+  );
+
+  async function exists(address: PublicKey): Promise<boolean> {
+    return (await provider.connection.getAccountInfo(address)) !== null;
+  }
+
+  // Fund a fresh wallet from the provider wallet
+  async function newWallet(): Promise<Keypair> {
+    const wallet = Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: wallet.publicKey,
+          lamports: LAMPORTS_PER_SOL,
+        })
+      )
+    );
+    return wallet;
+  }
+
+  // Self-mint a Token-2022 TrustToken, returning its data account
+  async function mintTrustToken(wallet: Keypair): Promise<PublicKey> {
+    const mint = Keypair.generate();
+    await trustToken.methods
+      .mintV2("Trust Token", "TRUST", "https://example.com/trust.json")
+      .accountsPartial({
+        minter: wallet.publicKey,
+        approver: null,
+        attestorRecord: null,
+        mint: mint.publicKey,
+        tokenAccount: getAssociatedTokenAddressSync(
+          mint.publicKey,
+          wallet.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+      })
+      .signers([wallet, mint])
+      .rpc();
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("trust_token"), mint.publicKey.toBuffer()],
+      trustToken.programId
+    )[0];
+  }
+
+  // Accounts of a create_card call that the client cannot derive from the IDL
+  async function cardAccounts(
+    issuer: PublicKey,
+    recipient: PublicKey,
+    cardType: number,
+    orderRef: PublicKey
+  ) {
+    const programState = await program.account.programState.fetch(programStatePda);
+    return {
+      reputationCard: PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("reputation_card"),
+          issuer.toBuffer(),
+          recipient.toBuffer(),
+          programState.totalCardsIssued.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0],
+      cardPair: PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("card_pair"),
+          issuer.toBuffer(),
+          recipient.toBuffer(),
+          Buffer.from([cardType]),
+        ],
+        program.programId
+      )[0],
+      orderRecord: PublicKey.findProgramAddressSync(
+        [Buffer.from("order_ref"), orderRef.toBuffer()],
+        program.programId
+      )[0],
+    };
+  }
+
+  before(async () => {
+    if (!(await exists(trustTokenStatePda))) {
+      await trustToken.methods
+        .initialize()
+        .accounts({ authority: authority.publicKey })
+        .rpc();
+    }
+    if (!(await exists(collectionGroupPda))) {
+      await trustToken.methods
+        .initializeCollectionV2("Trust Tokens", "TRUST", "https://example.com/group.json")
+        .accounts({ authority: authority.publicKey })
+        .rpc();
+    }
+    if (!(await exists(programStatePda))) {
+      await program.methods
+        .initialize()
+        .accounts({ authority: authority.publicKey })
+        .rpc();
+    }
+    await program.methods
+      .setMarketplaceAuthority(marketplace.publicKey)
+      .accounts({ authority: authority.publicKey })
+      .rpc();
+  });
+
+  it("Backs a card with a marketplace order only once", async () => {
+    console.log("\n📝 Test: Order references back a single card");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    const issuer = await newWallet();
+    const issuerTrustToken = await mintTrustToken(issuer);
+    const orderRef = Keypair.generate().publicKey;
+
+    const recipient = Keypair.generate().publicKey;
+    const first = await cardAccounts(issuer.publicKey, recipient, 0, orderRef);
+    await program.methods
+      .createCard({ trustworthy: {} }, "Smooth trade", 5, orderRef)
+      .accountsPartial({
+        issuer: issuer.publicKey,
+        issuerTrustToken,
+        recipient,
+        recipientTrustToken: null,
+        marketplaceAuthority: marketplace.publicKey,
+        ...first,
+      })
+      .signers([issuer, marketplace])
+      .rpc();
+
+    const card = await program.account.reputationCard.fetch(first.reputationCard);
+    assert.isTrue(card.verifiedPurchase, "Card should be a verified purchase");
+    assert.equal(card.orderRef.toString(), orderRef.toString());
+    const orderRecord = await program.account.orderRecord.fetch(first.orderRecord);
+    assert.equal(
+      orderRecord.reputationCard.toString(),
+      first.reputationCard.toString(),
+      "Order record should point at the card it backs"
+    );
+
+    // The same order cannot back a second card, even for another recipient
+    const otherRecipient = Keypair.generate().publicKey;
+    const second = await cardAccounts(issuer.publicKey, otherRecipient, 0, orderRef);
+    try {
+      await program.methods
+        .createCard({ trustworthy: {} }, "Smooth trade again", 5, orderRef)
+        .accountsPartial({
+          issuer: issuer.publicKey,
+          issuerTrustToken,
+          recipient: otherRecipient,
+          recipientTrustToken: null,
+          marketplaceAuthority: marketplace.publicKey,
+          ...second,
+        })
+        .signers([issuer, marketplace])
+        .rpc();
+      assert.fail("Reusing an order reference should fail");
+    } catch (error) {
+      assert.include(
+        (error.logs ?? []).join("\n"),
+        "already in use",
+        "The order record should already exist"
+      );
+    }
+    assert.isFalse(
+      await exists(second.reputationCard),
+      "No second card should have been created"
+    );
+
+    console.log("✅ Order reference rejected on reuse");
+  });
+});
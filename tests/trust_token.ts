@@ -1,11 +1,11 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program, BN } from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
 import { TrustToken } from "../target/types/trust_token";
-import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
-import { 
-  TOKEN_PROGRAM_ID, 
-  ASSOCIATED_TOKEN_PROGRAM_ID,
-  getAssociatedTokenAddress,
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
 import { assert } from "chai";
 
@@ -14,10 +14,8 @@ describe("trust_token", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
-  const programId = new PublicKey("3gUohiKvtQGZ2gXdimtvtVxy3JEFC9mTs3fLuo4ox5Ju");
   const program = new Program<TrustToken>(
     require("../target/idl/trust_token.json"),
-    programId,
     provider
   );
   const authority = provider.wallet as anchor.Wallet;
@@ -32,46 +30,199 @@ describe("trust_token", () => {
     [Buffer.from("program_state")],
     program.programId
   );
+  const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint_authority"), programStatePda.toBuffer()],
+    program.programId
+  );
+  const [collectionMintPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("collection_mint"), programStatePda.toBuffer()],
+    program.programId
+  );
+  const [collectionGroupPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("collection_group"), programStatePda.toBuffer()],
+    program.programId
+  );
 
   console.log("\n🔑 Test Configuration:");
   console.log("Program ID:", program.programId.toString());
   console.log("Authority:", authority.publicKey.toString());
   console.log("Program State PDA:", programStatePda.toString());
 
+  function pda(seed: string, key: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(seed), key.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  // Metaplex metadata and master edition PDAs of a mint
+  function metadataPda(mint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+  }
+
+  function masterEditionPda(mint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.toBuffer(),
+        Buffer.from("edition"),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+  }
+
+  async function exists(address: PublicKey): Promise<boolean> {
+    return (await provider.connection.getAccountInfo(address)) !== null;
+  }
+
+  async function expectError(call: Promise<unknown>, code: string) {
+    try {
+      await call;
+    } catch (error) {
+      assert.equal(error.error?.errorCode?.code, code);
+      return;
+    }
+    assert.fail(`Expected the transaction to fail with ${code}`);
+  }
+
+  // Fund a fresh wallet from the provider wallet, so each test mints into its own identity
+  async function newWallet(): Promise<Keypair> {
+    const wallet = Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: wallet.publicKey,
+          lamports: LAMPORTS_PER_SOL,
+        })
+      )
+    );
+    return wallet;
+  }
+
+  // Mint a TrustToken to `minter`, co-signed by `approver` when minting is gated
+  function mintToken(minter: Keypair, mint: Keypair, approver?: Keypair) {
+    return program.methods
+      .mint("Trust Token", "TRUST", "https://arweave.net/trust-token-metadata")
+      .accountsPartial({
+        minter: minter.publicKey,
+        approver: approver?.publicKey ?? null,
+        attestorRecord: null,
+        mint: mint.publicKey,
+        tokenAccount: getAssociatedTokenAddressSync(mint.publicKey, minter.publicKey),
+        metadata: metadataPda(mint.publicKey),
+        masterEdition: masterEditionPda(mint.publicKey),
+        collectionMetadata: metadataPda(collectionMintPda),
+        collectionMasterEdition: masterEditionPda(collectionMintPda),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([minter, mint, ...(approver ? [approver] : [])])
+      .rpc();
+  }
+
+  // Mint a TrustToken to a fresh wallet, returning its mint and data account
+  async function mintTrustToken() {
+    const mint = Keypair.generate();
+    await mintToken(await newWallet(), mint);
+    return { mint: mint.publicKey, trustToken: pda("trust_token", mint.publicKey) };
+  }
+
+  // Self-mint a Token-2022 TrustToken (no Metaplex accounts needed)
+  async function mintV2(wallet: Keypair): Promise<{ mint: PublicKey; trustToken: PublicKey }> {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintV2("Trust Token", "TRUST", "https://arweave.net/trust-token-v2")
+      .accountsPartial({
+        minter: wallet.publicKey,
+        approver: null,
+        attestorRecord: null,
+        mint: mint.publicKey,
+        tokenAccount: getAssociatedTokenAddressSync(
+          mint.publicKey,
+          wallet.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+      })
+      .signers([wallet, mint])
+      .rpc();
+    return { mint: mint.publicKey, trustToken: pda("trust_token", mint.publicKey) };
+  }
+
+  async function revoke(trustToken: PublicKey) {
+    await program.methods
+      .revokeVerification({ fraud: {} }, "Forged documents")
+      .accountsPartial({
+        authority: authority.publicKey,
+        attestorRecord: null,
+        trustToken,
+      })
+      .rpc();
+  }
+
+  // Burn a Token-2022 TrustToken held by `owner`
+  function burnV2(owner: Keypair, mint: PublicKey, withTombstone: boolean) {
+    return program.methods
+      .burnOwnToken()
+      .accountsPartial({
+        owner: owner.publicKey,
+        trustToken: pda("trust_token", mint),
+        mint,
+        tokenAccount: getAssociatedTokenAddressSync(
+          mint,
+          owner.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        metadata: null,
+        masterEdition: null,
+        tombstone: withTombstone ? pda("tombstone", owner.publicKey) : null,
+        recoveryInitiator: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenMetadataProgram: null,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  function setMintPolicy(mintPolicy) {
+    return program.methods
+      .setMintPolicy(mintPolicy)
+      .accounts({ authority: authority.publicKey })
+      .rpc();
+  }
+
   it("Initializes the TrustToken program", async () => {
     console.log("\n📝 Test 1: Initialize Program");
     console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
     try {
-      const tx = await program.methods
-        .initialize()
-        .accounts({
-          authority: authority.publicKey,
-          programState: programStatePda,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
-
-      console.log("✅ Transaction signature:", tx);
+      // Other test files may have initialized the program already
+      if (!(await exists(programStatePda))) {
+        const tx = await program.methods
+          .initialize()
+          .accounts({ authority: authority.publicKey })
+          .rpc();
+        console.log("✅ Transaction signature:", tx);
+      }
 
       // Fetch and verify program state
       const programState = await program.account.programState.fetch(programStatePda);
-      
+
       console.log("\n📊 Program State:");
       console.log("  Authority:", programState.authority.toString());
       console.log("  Total Minted:", programState.totalMinted.toString());
 
-      // Assertions
       assert.equal(
         programState.authority.toString(),
         authority.publicKey.toString(),
         "Authority should match wallet public key"
       );
-      assert.equal(
-        programState.totalMinted.toNumber(),
-        0,
-        "Total minted should be 0 initially"
-      );
+      assert.deepEqual(programState.mintPolicy, { open: {} }, "Minting should be open");
 
       console.log("\n✅ Program initialized successfully!");
     } catch (error) {
@@ -80,116 +231,99 @@ describe("trust_token", () => {
     }
   });
 
-  it("Mints a TrustToken NFT to a user", async () => {
-    console.log("\n📝 Test 2: Mint TrustToken NFT");
+  it("Initializes the TrustToken collections", async () => {
+    console.log("\n📝 Test 2: Initialize Collections");
     console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    // Generate a new recipient
-    const recipient = Keypair.generate();
-    console.log("Recipient address:", recipient.publicKey.toString());
+    if (!(await exists(collectionMintPda))) {
+      await program.methods
+        .initializeCollection("Trust Tokens", "TRUST", "https://arweave.net/trust-collection")
+        .accountsPartial({
+          authority: authority.publicKey,
+          collectionTokenAccount: getAssociatedTokenAddressSync(
+            collectionMintPda,
+            mintAuthorityPda,
+            true
+          ),
+          collectionMetadata: metadataPda(collectionMintPda),
+          collectionMasterEdition: masterEditionPda(collectionMintPda),
+        })
+        .rpc();
+    }
+    if (!(await exists(collectionGroupPda))) {
+      await program.methods
+        .initializeCollectionV2("Trust Tokens", "TRUST", "https://arweave.net/trust-collection")
+        .accounts({ authority: authority.publicKey })
+        .rpc();
+    }
 
-    // Generate a new mint keypair
-    const mint = Keypair.generate();
-    console.log("Mint address:", mint.publicKey.toString());
+    assert.isTrue(await exists(collectionMintPda), "Metaplex collection should exist");
+    assert.isTrue(await exists(collectionGroupPda), "Token-2022 group should exist");
+    console.log("\n✅ Collections ready");
+  });
 
-    // Derive PDAs
-    const [trustTokenPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("trust_token"), mint.publicKey.toBuffer()],
-      program.programId
-    );
+  it("Mints a TrustToken NFT to a user", async () => {
+    console.log("\n📝 Test 3: Mint TrustToken NFT");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    const tokenAccount = await getAssociatedTokenAddress(
-      mint.publicKey,
-      recipient.publicKey
-    );
+    // The minter signs for their own token while minting is open
+    const minter = await newWallet();
+    console.log("Minter address:", minter.publicKey.toString());
 
-    // Derive metadata PDA (Metaplex standard)
-    const [metadataPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
+    const mint = Keypair.generate();
+    console.log("Mint address:", mint.publicKey.toString());
 
-    // Derive master edition PDA (Metaplex standard)
-    const [masterEditionPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-        Buffer.from("edition"),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
+    const trustTokenPda = pda("trust_token", mint.publicKey);
+    const identityPda = pda("identity", minter.publicKey);
 
     console.log("\n🔑 Derived Accounts:");
     console.log("  TrustToken PDA:", trustTokenPda.toString());
-    console.log("  Token Account:", tokenAccount.toString());
-    console.log("  Metadata PDA:", metadataPda.toString());
-    console.log("  Master Edition PDA:", masterEditionPda.toString());
+    console.log("  Identity PDA:", identityPda.toString());
 
-    // NFT metadata
-    const name = "Trust Token #1";
-    const symbol = "TRUST";
-    const uri = "https://arweave.net/trust-token-metadata";
+    const before = await program.account.programState.fetch(programStatePda);
 
     try {
-      const tx = await program.methods
-        .mint(name, symbol, uri)
-        .accounts({
-          authority: authority.publicKey,
-          payer: authority.publicKey,
-          recipient: recipient.publicKey,
-          programState: programStatePda,
-          mint: mint.publicKey,
-          tokenAccount: tokenAccount,
-          trustToken: trustTokenPda,
-          metadata: metadataPda,
-          masterEdition: masterEditionPda,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-          rent: SYSVAR_RENT_PUBKEY,
-        })
-        .signers([mint])
-        .rpc();
+      const tx = await mintToken(minter, mint);
 
       console.log("\n✅ Transaction signature:", tx);
 
       // Fetch and verify TrustToken account
       const trustToken = await program.account.trustToken.fetch(trustTokenPda);
-      
+      const identity = await program.account.identity.fetch(identityPda);
+      const programState = await program.account.programState.fetch(programStatePda);
+
       console.log("\n📊 TrustToken Data:");
       console.log("  Owner:", trustToken.owner.toString());
       console.log("  Mint:", trustToken.mint.toString());
       console.log("  Is Verified:", trustToken.isVerified);
-      console.log("  Minted At:", new Date(trustToken.mintedAt.toNumber() * 1000).toISOString());
-
-      // Fetch updated program state
-      const programState = await program.account.programState.fetch(programStatePda);
       console.log("  Total Minted:", programState.totalMinted.toString());
 
-      // Assertions
       assert.equal(
         trustToken.owner.toString(),
-        recipient.publicKey.toString(),
-        "Owner should match recipient"
+        minter.publicKey.toString(),
+        "Owner should match minter"
       );
+      assert.equal(trustToken.mint.toString(), mint.publicKey.toString(), "Mint should match");
+      assert.isTrue(trustToken.isVerified, "Token should be verified");
       assert.equal(
-        trustToken.mint.toString(),
-        mint.publicKey.toString(),
-        "Mint should match"
+        identity.trustToken.toString(),
+        trustTokenPda.toString(),
+        "Identity should point at the TrustToken"
       );
-      assert.isTrue(trustToken.isVerified, "Token should be verified");
       assert.equal(
         programState.totalMinted.toNumber(),
-        1,
-        "Total minted should be 1"
+        before.totalMinted.toNumber() + 1,
+        "Total minted should increase by 1"
       );
 
+      // A wallet can only hold one TrustToken
+      try {
+        await mintToken(minter, Keypair.generate());
+        assert.fail("A second mint from the same wallet should fail");
+      } catch (error) {
+        assert.include((error.logs ?? []).join("\n"), "already in use");
+      }
+
       console.log("\n✅ TrustToken NFT minted successfully!");
     } catch (error) {
       console.error("❌ Error minting TrustToken:", error);
@@ -197,88 +331,65 @@ describe("trust_token", () => {
     }
   });
 
-  it("Revokes verification of a TrustToken", async () => {
-    console.log("\n📝 Test 3: Revoke Verification");
+  it("Requires the authority's co-signature when minting is gated", async () => {
+    console.log("\n📝 Test 4: Authority-Only Minting");
     console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    // Use the mint from the previous test (we'll create a new one for this test)
-    const recipient = Keypair.generate();
-    const mint = Keypair.generate();
-
-    const [trustTokenPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("trust_token"), mint.publicKey.toBuffer()],
-      program.programId
-    );
-
-    const tokenAccount = await getAssociatedTokenAddress(
-      mint.publicKey,
-      recipient.publicKey
-    );
+    await setMintPolicy({ authorityOnly: {} });
+    try {
+      const minter = await newWallet();
+      await expectError(mintToken(minter, Keypair.generate()), "UnauthorizedMint");
+      await expectError(
+        mintToken(minter, Keypair.generate(), await newWallet()),
+        "UnauthorizedMint"
+      );
 
-    const [metadataPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
+      const mint = Keypair.generate();
+      await mintToken(minter, mint, authority.payer);
+      const trustToken = await program.account.trustToken.fetch(pda("trust_token", mint.publicKey));
+      assert.equal(trustToken.owner.toString(), minter.publicKey.toString());
+    } finally {
+      await setMintPolicy({ open: {} });
+    }
 
-    const [masterEditionPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-        Buffer.from("edition"),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
+    console.log("\n✅ Gated minting enforced");
+  });
 
-    // First, mint a token
-    console.log("Minting token first...");
-    await program.methods
-      .mint("Trust Token #2", "TRUST", "https://arweave.net/trust-token-2")
-      .accounts({
-        authority: authority.publicKey,
-        payer: authority.publicKey,
-        recipient: recipient.publicKey,
-        programState: programStatePda,
-        mint: mint.publicKey,
-        tokenAccount: tokenAccount,
-        trustToken: trustTokenPda,
-        metadata: metadataPda,
-        masterEdition: masterEditionPda,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-      })
-      .signers([mint])
-      .rpc();
+  it("Revokes verification of a TrustToken", async () => {
+    console.log("\n📝 Test 5: Revoke Verification");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
+    const { mint, trustToken: trustTokenPda } = await mintTrustToken();
     console.log("✅ Token minted");
 
-    // Now revoke verification
     try {
       const tx = await program.methods
-        .revokeVerification()
-        .accounts({
+        .revokeVerification({ fraud: {} }, "Forged documents")
+        .accountsPartial({
           authority: authority.publicKey,
-          programState: programStatePda,
+          attestorRecord: null,
           trustToken: trustTokenPda,
         })
         .rpc();
 
       console.log("✅ Revocation transaction signature:", tx);
 
-      // Fetch and verify
       const trustToken = await program.account.trustToken.fetch(trustTokenPda);
-      
+      const log = await program.account.revocationLog.fetch(pda("revocation_log", mint));
+
       console.log("\n📊 TrustToken After Revocation:");
       console.log("  Is Verified:", trustToken.isVerified);
+      console.log("  Log Entries:", log.entries.length);
 
       assert.isFalse(trustToken.isVerified, "Token should not be verified");
+      assert.equal(trustToken.revokedBy.toString(), authority.publicKey.toString());
+      const entry = log.entries[log.entries.length - 1];
+      assert.deepEqual(entry.action, { revoked: {} });
+      assert.deepEqual(entry.reason, { fraud: {} });
+      assert.equal(entry.memo, "Forged documents");
+
+      // Revoking twice is rejected
+      await expectError(revoke(trustTokenPda), "AlreadyRevoked");
 
       console.log("\n✅ Verification revoked successfully!");
     } catch (error) {
@@ -288,96 +399,38 @@ describe("trust_token", () => {
   });
 
   it("Restores verification of a TrustToken", async () => {
-    console.log("\n📝 Test 4: Restore Verification");
+    console.log("\n📝 Test 6: Restore Verification");
     console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    // Use the mint from the previous test
-    const recipient = Keypair.generate();
-    const mint = Keypair.generate();
-
-    const [trustTokenPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("trust_token"), mint.publicKey.toBuffer()],
-      program.programId
-    );
-
-    const tokenAccount = await getAssociatedTokenAddress(
-      mint.publicKey,
-      recipient.publicKey
-    );
-
-    const [metadataPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
-
-    const [masterEditionPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-        Buffer.from("edition"),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
-
-    // Mint and revoke first
-    console.log("Minting and revoking token first...");
-    await program.methods
-      .mint("Trust Token #3", "TRUST", "https://arweave.net/trust-token-3")
-      .accounts({
-        authority: authority.publicKey,
-        payer: authority.publicKey,
-        recipient: recipient.publicKey,
-        programState: programStatePda,
-        mint: mint.publicKey,
-        tokenAccount: tokenAccount,
-        trustToken: trustTokenPda,
-        metadata: metadataPda,
-        masterEdition: masterEditionPda,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-      })
-      .signers([mint])
-      .rpc();
-
-    await program.methods
-      .revokeVerification()
-      .accounts({
-        authority: authority.publicKey,
-        programState: programStatePda,
-        trustToken: trustTokenPda,
-      })
-      .rpc();
-
+    const { mint, trustToken: trustTokenPda } = await mintTrustToken();
+    await revoke(trustTokenPda);
     console.log("✅ Token minted and revoked");
 
-    // Now restore verification
     try {
       const tx = await program.methods
-        .restoreVerification()
-        .accounts({
+        .restoreVerification("Documents re-checked")
+        .accountsPartial({
           authority: authority.publicKey,
-          programState: programStatePda,
+          attestorRecord: null,
           trustToken: trustTokenPda,
         })
         .rpc();
 
       console.log("✅ Restoration transaction signature:", tx);
 
-      // Fetch and verify
       const trustToken = await program.account.trustToken.fetch(trustTokenPda);
-      
+      const log = await program.account.revocationLog.fetch(pda("revocation_log", mint));
+
       console.log("\n📊 TrustToken After Restoration:");
       console.log("  Is Verified:", trustToken.isVerified);
 
       assert.isTrue(trustToken.isVerified, "Token should be verified again");
+      assert.isNull(trustToken.revokedAt, "Revocation should be cleared");
+      assert.deepEqual(
+        log.entries.map((entry) => Object.keys(entry.action)[0]),
+        ["revoked", "restored"],
+        "The log should keep both actions"
+      );
 
       console.log("\n✅ Verification restored successfully!");
     } catch (error) {
@@ -386,12 +439,228 @@ describe("trust_token", () => {
     }
   });
 
+  it("Burns a holder's own TrustToken", async () => {
+    console.log("\n📝 Test 7: Burn Own TrustToken");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    const holder = await newWallet();
+    const { mint, trustToken } = await mintV2(holder);
+    const tokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      holder.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const before = await program.account.programState.fetch(programStatePda);
+
+    await burnV2(holder, mint, false);
+
+    const programState = await program.account.programState.fetch(programStatePda);
+    assert.equal(
+      programState.totalBurned.toNumber(),
+      before.totalBurned.toNumber() + 1,
+      "Total burned should increase by 1"
+    );
+    assert.isFalse(await exists(tokenAccount), "Token account should be closed");
+    assert.isFalse(await exists(trustToken), "TrustToken account should be closed");
+    assert.isFalse(await exists(pda("identity", holder.publicKey)), "Identity should be closed");
+    assert.isFalse(
+      await exists(pda("tombstone", holder.publicKey)),
+      "A verified token leaves no tombstone"
+    );
+
+    // The wallet can mint again
+    await mintV2(holder);
+    console.log("\n✅ TrustToken burned and re-minted");
+  });
+
+  it("Tombstones a wallet that burns a revoked TrustToken", async () => {
+    console.log("\n📝 Test 8: Burn Revoked TrustToken");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    const holder = await newWallet();
+    const { mint, trustToken } = await mintV2(holder);
+    await revoke(trustToken);
+
+    await expectError(burnV2(holder, mint, false), "MissingTombstone");
+    await burnV2(holder, mint, true);
+
+    const tombstone = await program.account.tombstone.fetch(pda("tombstone", holder.publicKey));
+    assert.equal(tombstone.mint.toString(), mint.toString());
+    assert.isNotNull(tombstone.revokedAt, "Tombstone should record the revocation");
+    assert.isTrue(
+      await exists(pda("revocation_log", mint)),
+      "The revocation log should survive the burn"
+    );
+
+    // Minting is open, but a tombstoned wallet needs approval
+    await expectError(mintV2(holder), "UnauthorizedMint");
+    console.log("\n✅ Revoked TrustToken burned and wallet tombstoned");
+  });
+
+  it("Runs a guardian recovery up to its delay", async () => {
+    console.log("\n📝 Test 9: Guardian Recovery");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    const holder = await newWallet();
+    const { mint, trustToken } = await mintV2(holder);
+    const [first, second] = [await newWallet(), await newWallet()];
+    const newOwner = await newWallet();
+    const recoveryPda = pda("recovery", holder.publicKey);
+
+    await program.methods
+      .setGuardians([first.publicKey, second.publicKey], 2)
+      .accounts({ owner: holder.publicKey })
+      .signers([holder])
+      .rpc();
+
+    const initiate = (guardian: Keypair) =>
+      program.methods
+        .initiateRecovery(newOwner.publicKey)
+        .accountsPartial({ guardian: guardian.publicKey, identity: pda("identity", holder.publicKey) })
+        .signers([guardian])
+        .rpc();
+
+    // The owner can cancel a pending recovery, refunding the initiator
+    await initiate(first);
+    let request = await program.account.recoveryRequest.fetch(recoveryPda);
+    assert.equal(request.approvals.length, 1, "Initiating counts as an approval");
+    assert.isNull(request.approvedAt, "One of two approvals is below the threshold");
+    await program.methods
+      .cancelRecovery()
+      .accountsPartial({ owner: holder.publicKey, initiator: first.publicKey })
+      .signers([holder])
+      .rpc();
+    assert.isFalse(await exists(recoveryPda), "Cancelled request should be closed");
+
+    // Reaching the threshold starts the delay
+    await initiate(first);
+    await program.methods
+      .approveRecovery()
+      .accountsPartial({ guardian: second.publicKey, identity: pda("identity", holder.publicKey) })
+      .signers([second])
+      .rpc();
+    request = await program.account.recoveryRequest.fetch(recoveryPda);
+    assert.equal(request.approvals.length, 2);
+    assert.isNotNull(request.approvedAt, "Threshold reached");
+
+    // An approved request cannot be withdrawn as stalled
+    await expectError(
+      program.methods
+        .expireRecovery()
+        .accountsPartial({
+          guardian: first.publicKey,
+          identity: pda("identity", holder.publicKey),
+          initiator: first.publicKey,
+        })
+        .signers([first])
+        .rpc(),
+      "RecoveryAlreadyApproved"
+    );
+
+    // The new wallet has to wait out the delay
+    const newMint = Keypair.generate();
+    await expectError(
+      program.methods
+        .recoverWithGuardians("Trust Token", "TRUST", "https://arweave.net/trust-token-v2")
+        .accountsPartial({
+          migration: {
+            newOwner: newOwner.publicKey,
+            oldTrustToken: trustToken,
+            oldMint: mint,
+            oldTokenAccount: getAssociatedTokenAddressSync(
+              mint,
+              holder.publicKey,
+              false,
+              TOKEN_2022_PROGRAM_ID
+            ),
+            oldMetadata: null,
+            oldMasterEdition: null,
+            mint: newMint.publicKey,
+            tokenAccount: getAssociatedTokenAddressSync(
+              newMint.publicKey,
+              newOwner.publicKey,
+              false,
+              TOKEN_2022_PROGRAM_ID
+            ),
+            metadata: null,
+            masterEdition: null,
+            collectionMint: null,
+            collectionMetadata: null,
+            collectionMasterEdition: null,
+            collectionGroup: collectionGroupPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            tokenMetadataProgram: null,
+          },
+          initiator: first.publicKey,
+        })
+        .signers([newOwner, newMint])
+        .rpc(),
+      "RecoveryDelayNotElapsed"
+    );
+
+    // Burning the token closes the open request and refunds its initiator
+    await program.methods
+      .burnOwnToken()
+      .accountsPartial({
+        owner: holder.publicKey,
+        trustToken,
+        mint,
+        tokenAccount: getAssociatedTokenAddressSync(
+          mint,
+          holder.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        metadata: null,
+        masterEdition: null,
+        tombstone: null,
+        recoveryInitiator: first.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenMetadataProgram: null,
+      })
+      .signers([holder])
+      .rpc();
+    assert.isFalse(await exists(recoveryPda), "Burning should close the recovery request");
+    console.log("\n✅ Recovery flow checked");
+  });
+
+  it("Rejects reports of a TrustToken its owner still holds", async () => {
+    console.log("\n📝 Test 10: Report Transferred Token");
+    console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    const holder = await newWallet();
+    const { mint, trustToken } = await mintV2(holder);
+
+    await expectError(
+      program.methods
+        .reportTransferredToken()
+        .accountsPartial({
+          reporter: authority.publicKey,
+          trustToken,
+          tokenAccount: getAssociatedTokenAddressSync(
+            mint,
+            holder.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID
+          ),
+        })
+        .rpc(),
+      "TokenNotTransferred"
+    );
+
+    const token = await program.account.trustToken.fetch(trustToken);
+    assert.isTrue(token.isVerified, "A rejected report leaves the token verified");
+    assert.isNull(token.compromisedAt);
+    console.log("\n✅ Report rejected");
+  });
+
   it("Displays final program statistics", async () => {
-    console.log("\n📝 Test 5: Final Statistics");
+    console.log("\n📝 Test 11: Final Statistics");
     console.log("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
     const programState = await program.account.programState.fetch(programStatePda);
-    
+
     console.log("\n📊 Final Program State:");
     console.log("  Authority:", programState.authority.toString());
     console.log("  Total Minted:", programState.totalMinted.toString());